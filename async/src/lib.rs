@@ -182,4 +182,69 @@ mod thread {
     //实现了Sync的类型可以在线程间安全的共享(通过引用)
     //这里还有一个潜在的依赖：一个类型要在线程间安全的共享的前提是，指向它的引用必须能在线程间传递。因为如果引用都不能被传递，我们就无法在多个线程间使用引用去访问同一个数据了。
     //由上可知，若类型T的引用&T是Send，则T是Sync。
-}
\ No newline at end of file
+
+    //问题：能不能用Arc<Mutex<chapter_4_5::List<T>>>把双向链表当作channel的接收端，让另一个线程往里push_back？
+    //答：不行。chapter_4_5的双向链表节点是Rc<RefCell<Node>>，Rc的引用计数不是原子的，所以它没有实现Send，
+    //   Mutex<T>只有在T: Send时才是Sync，包了Arc<Mutex<..>>也传不进thread::spawn。而且它在chapter_4_5的main.rs里，
+    //   不是库，别的crate也用不了。这里换成chapter_2_3里用Box实现的单链表，T: Send时它就是Send。
+
+    ///## 把channel接到链表上
+    /// 后台线程不断从Receiver里取值追加到链表尾部，所有Sender都被丢弃后线程结束
+    ///
+    /// 单链表没有尾指针，每追加一次都要走一遍链表。所以阻塞等到一个值后，顺手把channel里已经到了的值一起取出来，
+    /// 一批只走一次链表，避免收N个值要O(N²)
+    struct ListSink<T> {
+        list: Arc<Mutex<chapter_2_3::List<T>>>,
+    }
+
+    impl<T: Send + 'static> ListSink<T> {
+        fn spawn(rx: mpsc::Receiver<T>) -> (ListSink<T>, thread::JoinHandle<()>) {
+            let sink = ListSink { list: Arc::new(Mutex::new(chapter_2_3::List::new())) };
+            let consumer = ListSink { list: Arc::clone(&sink.list) };
+            let handle = thread::spawn(move || {
+                while let Ok(first) = rx.recv() {
+                    let batch = std::iter::once(first).chain(rx.try_iter());
+                    consumer.list.lock().unwrap().collect_into(batch);
+                }
+            });
+            (sink, handle)
+        }
+
+        //单链表没有尾指针，追加到尾部要走一遍链表
+        fn push_back(&self, elem: T) {
+            self.list.lock().unwrap().collect_into(Some(elem));
+        }
+    }
+
+    #[test]
+    fn list_sink() {
+        let (tx, rx) = mpsc::channel();
+        let (sink, handle) = ListSink::spawn(rx);
+        for i in 1..=5 {
+            tx.send(i).unwrap();
+        }
+        drop(tx);
+        handle.join().unwrap();
+        sink.push_back(6);
+        let list = sink.list.lock().unwrap();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn list_sink_many() {
+        //一条一条追加的话是O(N²)，十万个值在debug下要十几秒，按批追加很快就能做完
+        const N: usize = 100_000;
+        let (tx, rx) = mpsc::channel();
+        for i in 0..N {
+            tx.send(i).unwrap();
+        }
+        drop(tx);
+        let start = std::time::Instant::now();
+        let (sink, handle) = ListSink::spawn(rx);
+        handle.join().unwrap();
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+        let list = sink.list.lock().unwrap();
+        assert_eq!(list.len(), N);
+        assert!(list.iter().copied().eq(0..N));
+    }
+}
@@ -1,6 +1,8 @@
 // `block_on`会阻塞当前线程直到指定的`Future`执行完成，这种阻塞当前线程以等待任务完成的方式较为简单、粗暴，
 // 好在其它运行时的执行器(executor)会提供更加复杂的行为，例如将多个`future`调度到同一个线程上执行。
 use futures::executor::block_on;
+use std::pin::Pin;
+use std::task::Context;
 
 /// # async编程入门
 async fn hello_world() {
@@ -21,7 +23,10 @@ fn main1() {
 }
 
 
-async fn learn_song() -> Song { /* ... */ }
+// 示例用的占位类型，只是为了让下面的代码能编译
+struct Song;
+
+async fn learn_song() -> Song { /* ... */ Song }
 
 async fn sing_song(song: Song) { /* ... */ }
 
@@ -53,16 +58,31 @@ fn main() {
 
 ///# 底层探秘: Future执行器与任务调度
 /// 首先，来给出 Future 的定义：它是一个能产出值的异步计算(虽然该值可能为空，例如 () )。光看这个定义，可能会觉得很空洞，我们来看看一个简化版的 Future 特征:
-trait SimpleFuture {
+pub trait SimpleFuture {
     type Output;
     fn poll(&mut self, wake: fn()) -> Poll<Self::Output>;
 }
 
-enum Poll<T> {
+pub enum Poll<T> {
     Ready(T),
     Pending,
 }
 
+/// 示例用的占位 socket，永远没有数据可读
+pub struct Socket;
+
+impl Socket {
+    fn has_data_to_read(&self) -> bool {
+        false
+    }
+
+    fn read_buf(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn set_readable_callback(&self, _wake: fn()) {}
+}
+
 ///考虑一个需要从 socket 读取数据的场景：如果有数据，可以直接读取数据并返回 Poll::Ready(data)， 但如果没有数据，Future 会被阻塞且不会再继续执行，此时它会注册一个 wake 函数，当 socket 数据准备好时，该函数将被调用以通知执行器：我们的 Future 已经准备好了，可以继续执行。
 ///
 /// 下面的 SocketRead 结构体就是一个 Future:
@@ -93,37 +113,58 @@ impl SimpleFuture for SocketRead<'_> {
 /// 一个SimpleFuture，它会并发地运行两个Future直到它们完成
 ///
 /// 之所以可以并发，是因为两个Future的轮询可以交替进行，一个阻塞，另一个就可以立刻执行，反之亦然
-pub struct Join<FutureA, FutureB> {
+///
+/// 完成后的输出会缓存下来，所以 `Join` 返回 `Ready` 之后再被 poll 也不会 panic，而是再次返回同样的结果
+pub struct Join<FutureA: SimpleFuture, FutureB: SimpleFuture> {
     // 结构体的每个字段都包含一个Future，可以运行直到完成.
     // 如果Future完成后，字段会被设置为 `None`. 这样Future完成后，就不会再被轮询
     a: Option<FutureA>,
     b: Option<FutureB>,
+    // Future完成后，它的输出保存在这里，两个都有值时就是最终结果
+    a_output: Option<FutureA::Output>,
+    b_output: Option<FutureB::Output>,
+}
+
+impl<FutureA: SimpleFuture, FutureB: SimpleFuture> Join<FutureA, FutureB> {
+    pub fn new(a: FutureA, b: FutureB) -> Self {
+        Join {
+            a: Some(a),
+            b: Some(b),
+            a_output: None,
+            b_output: None,
+        }
+    }
 }
 
 impl<FutureA, FutureB> SimpleFuture for Join<FutureA, FutureB>
     where
-        FutureA: SimpleFuture<Output=()>,
-        FutureB: SimpleFuture<Output=()>,
+        FutureA: SimpleFuture,
+        FutureB: SimpleFuture,
+        FutureA::Output: Clone,
+        FutureB::Output: Clone,
 {
-    type Output = ();
+    type Output = (FutureA::Output, FutureB::Output);
     fn poll(&mut self, wake: fn()) -> Poll<Self::Output> {
         // 尝试去完成一个 Future `a`
         if let Some(a) = &mut self.a {
-            if let Poll::Ready(()) = a.poll(wake) {
+            if let Poll::Ready(output) = a.poll(wake) {
+                self.a_output = Some(output);
                 self.a.take();
             }
         }
 
         // 尝试去完成一个 Future `b`
         if let Some(b) = &mut self.b {
-            if let Poll::Ready(()) = b.poll(wake) {
+            if let Poll::Ready(output) = b.poll(wake) {
+                self.b_output = Some(output);
                 self.b.take();
             }
         }
 
-        if self.a.is_none() && self.b.is_none() {
+        if let (Some(a), Some(b)) = (&self.a_output, &self.b_output) {
             // 两个 Future都已完成 - 我们可以成功地返回了
-            Poll::Ready(())
+            // 结果仍然留在缓存里，之后再被poll会返回同样的值
+            Poll::Ready((a.clone(), b.clone()))
         } else {
             // 至少还有一个 Future 没有完成任务，因此返回 `Poll::Pending`.
             // 当该 Future 再次准备好时，通过调用`wake()`函数来继续执行
@@ -133,6 +174,63 @@ impl<FutureA, FutureB> SimpleFuture for Join<FutureA, FutureB>
 }
 
 
+///## 同时运行任意多个 Future
+/// 和 `Join` 一样，但是 Future 的个数不固定，全部完成后按原来的顺序返回所有输出
+///
+/// 为了公平，每次 poll 的起始下标都会往后挪一个(轮转)，这样不会总是同一个 Future 排在最前面，
+/// 在这个简化的调度器里，不会有哪个 Future 因为位置靠后而一直吃亏
+pub struct JoinAll<F: SimpleFuture> {
+    futures: Vec<Option<F>>,
+    outputs: Vec<Option<F::Output>>,
+    // 已经被 poll 的次数，也决定了下一次从哪个下标开始
+    polls: usize,
+}
+
+impl<F: SimpleFuture> JoinAll<F> {
+    pub fn new(futures: Vec<F>) -> Self {
+        let outputs = futures.iter().map(|_| None).collect();
+        JoinAll {
+            futures: futures.into_iter().map(Some).collect(),
+            outputs,
+            polls: 0,
+        }
+    }
+
+    /// `JoinAll` 自己被 poll 了多少次
+    pub fn poll_count(&self) -> usize {
+        self.polls
+    }
+}
+
+impl<F> SimpleFuture for JoinAll<F>
+    where
+        F: SimpleFuture,
+        F::Output: Clone,
+{
+    type Output = Vec<F::Output>;
+    fn poll(&mut self, wake: fn()) -> Poll<Self::Output> {
+        let n = self.futures.len();
+        let start = if n == 0 { 0 } else { self.polls % n };
+        self.polls += 1;
+        // 从 start 开始绕一圈，完成了的 Future 不再被 poll
+        for i in (start..n).chain(0..start) {
+            if let Some(future) = &mut self.futures[i] {
+                if let Poll::Ready(output) = future.poll(wake) {
+                    self.outputs[i] = Some(output);
+                    self.futures[i] = None;
+                }
+            }
+        }
+
+        if self.outputs.iter().all(Option::is_some) {
+            Poll::Ready(self.outputs.iter().flatten().cloned().collect())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+
 ///## 多个Future也可以一个接一个的连续运行
 /// 一个SimpleFuture, 它使用顺序的方式，一个接一个地运行两个Future
 ///
@@ -165,6 +263,82 @@ impl<FutureA, FutureB> SimpleFuture for AndThenFut<FutureA, FutureB>
 }
 
 
+///## 多个 Future 赛跑
+/// 按下标从小到大依次 poll 每个还在的 Future，返回第一个完成的输出，其余的 Future 全部被丢弃(`futures` 会被清空)。
+///
+/// 同一轮里有多个 Future 都能完成时，下标小的赢，下标更大的甚至不会被 poll，这样结果是确定的
+pub fn race<F: SimpleFuture>(futures: &mut Vec<Option<F>>, wake: fn()) -> Poll<F::Output> {
+    let mut winner = None;
+    for future in futures.iter_mut().flatten() {
+        if let Poll::Ready(output) = future.poll(wake) {
+            winner = Some(output);
+            break;
+        }
+    }
+    match winner {
+        Some(output) => {
+            futures.clear();
+            Poll::Ready(output)
+        }
+        None => Poll::Pending,
+    }
+}
+
+
+///## select：两个 Future 谁先完成就处理谁
+/// `simple_select!` 把两个 Future 各 poll 一次，先完成的那个的输出交给对应的处理函数，整个表达式的值是 `Poll<R>`，
+/// 两个处理函数必须返回同一种类型 R，都没完成时是 `Poll::Pending`，调用方下次再 poll。
+///
+/// 和 `race` 一样，同一轮里两个都能完成时排在前面的赢，后面的那个不会被 poll
+///
+/// ```ignore
+/// let result = simple_select! {
+///     wake,
+///     a => |x| x * 2,
+///     b => |s| s.len(),
+/// };
+/// ```
+#[allow(unused_macros)] // 教程里只有测试用到了它
+macro_rules! simple_select {
+    ($wake:expr, $a:expr => |$x:pat_param| $on_a:expr, $b:expr => |$y:pat_param| $on_b:expr $(,)?) => {
+        match SimpleFuture::poll(&mut $a, $wake) {
+            Poll::Ready($x) => Poll::Ready($on_a),
+            Poll::Pending => match SimpleFuture::poll(&mut $b, $wake) {
+                Poll::Ready($y) => Poll::Ready($on_b),
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    };
+}
+
+
+///## 产出多个值：Stream
+/// Future 只产出一个值，Stream 则可以被反复 poll，每次产出一个值，返回 `Poll::Ready(None)` 表示流已经结束
+pub trait SimpleStream {
+    type Item;
+    fn poll_next(&mut self, wake: fn()) -> Poll<Option<Self::Item>>;
+}
+
+/// 把 chapter_2_3 的链表包装成一个 Stream，每次 poll 从链表头弹出一个元素
+pub struct ListStream<T> {
+    list: chapter_2_3::List<T>,
+}
+
+impl<T> ListStream<T> {
+    pub fn new(list: chapter_2_3::List<T>) -> Self {
+        ListStream { list }
+    }
+}
+
+impl<T> SimpleStream for ListStream<T> {
+    type Item = T;
+    fn poll_next(&mut self, _wake: fn()) -> Poll<Option<Self::Item>> {
+        // 链表里的元素已经就绪，不需要注册 wake
+        Poll::Ready(self.list.pop())
+    }
+}
+
+
 ///##真实的 Future 特征有何不同之处
 /// - 首先这里多了一个 Pin ，关于它我们会在后面章节详细介绍，现在你只需要知道使用它可以创建一个无法被移动的 Future ，因为无法被移动，因此它将具有固定的内存地址，意味着我们可以存储它的指针(如果内存地址可能会变动，那存储指针地址将毫无意义！)，也意味着可以实现一个自引用数据结构: struct MyFut { a: i32, ptr_to_a: *const i32 }。 而对于 async/await 来说，Pin 是不可或缺的关键特性。
 /// - 其次，从 wake: fn() 变成了 &mut Context<'_> 。意味着 wake 函数可以携带数据了，为何要携带数据？考虑一个真实世界的场景，一个复杂应用例如web服务器可能有数千连接同时在线，那么同时就有数千 Future 在被同时管理着，如果不能携带数据，当一个 Future 调用 wake 后，执行器该如何知道是哪个 Future 调用了 wake ,然后进一步去 poll 对应的 Future ？没有办法！那之前的例子为啥就可以使用没有携带数据的 wake ？ 因为足够简单，不存在歧义性。
@@ -177,4 +351,151 @@ trait Future {
         // 其次将`wake: fn()` 修改为 `cx: &mut Context<'_>`:
         cx: &mut Context<'_>,
     ) -> Poll<Self::Output>;
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop_wake() {}
+
+    /// 被 poll 若干次之后才完成的 Future
+    struct CountDown {
+        polls_left: u32,
+        value: i32,
+    }
+
+    impl SimpleFuture for CountDown {
+        type Output = i32;
+        fn poll(&mut self, _wake: fn()) -> Poll<Self::Output> {
+            if self.polls_left == 0 {
+                Poll::Ready(self.value)
+            } else {
+                self.polls_left -= 1;
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn list_stream() {
+        let mut list = chapter_2_3::List::new();
+        list.push(3);
+        list.push(2);
+        list.push(1);
+
+        let mut stream = ListStream::new(list);
+        let mut items = Vec::new();
+        loop {
+            match stream.poll_next(noop_wake) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => break,
+                Poll::Pending => {}
+            }
+        }
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn join_polled_after_ready() {
+        let mut join = Join::new(
+            CountDown { polls_left: 1, value: 1 },
+            CountDown { polls_left: 0, value: 2 },
+        );
+        assert!(matches!(join.poll(noop_wake), Poll::Pending));
+        assert!(matches!(join.poll(noop_wake), Poll::Ready((1, 2))));
+        // 已经完成后再 poll 两次，结果不变
+        assert!(matches!(join.poll(noop_wake), Poll::Ready((1, 2))));
+        assert!(matches!(join.poll(noop_wake), Poll::Ready((1, 2))));
+    }
+
+    /// 把自己的 id 记到共享的日志里，可以看出每一轮的 poll 顺序
+    struct Logged {
+        id: usize,
+        polls_left: u32,
+        log: std::rc::Rc<std::cell::RefCell<Vec<usize>>>,
+    }
+
+    impl SimpleFuture for Logged {
+        type Output = usize;
+        fn poll(&mut self, _wake: fn()) -> Poll<Self::Output> {
+            self.log.borrow_mut().push(self.id);
+            if self.polls_left == 0 {
+                Poll::Ready(self.id)
+            } else {
+                self.polls_left -= 1;
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn join_all_round_robin() {
+        let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let futures = (0..3)
+            .map(|id| Logged { id, polls_left: 2, log: log.clone() })
+            .collect();
+        let mut join = JoinAll::new(futures);
+        assert!(matches!(join.poll(noop_wake), Poll::Pending));
+        assert!(matches!(join.poll(noop_wake), Poll::Pending));
+        // 第三轮全部完成，输出仍然按原来的顺序排
+        assert!(matches!(join.poll(noop_wake), Poll::Ready(ref v) if *v == vec![0, 1, 2]));
+        assert_eq!(join.poll_count(), 3);
+        // 每一轮的起点都往后挪一个，没有哪个 Future 总是排在最前面
+        assert_eq!(*log.borrow(), vec![0, 1, 2, 1, 2, 0, 2, 0, 1]);
+    }
+
+    #[test]
+    fn join_all_skips_finished() {
+        let log = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let futures = (0..3)
+            .map(|id| Logged { id, polls_left: id as u32, log: log.clone() })
+            .collect();
+        let mut join = JoinAll::new(futures);
+        let mut polls = 0;
+        while let Poll::Pending = join.poll(noop_wake) {
+            polls += 1;
+            assert!(polls < 10);
+        }
+        // 完成了的 0 号之后不再被 poll
+        assert_eq!(*log.borrow(), vec![0, 1, 2, 1, 2, 2]);
+    }
+
+    #[test]
+    fn join_all_empty() {
+        let mut join = JoinAll::<CountDown>::new(Vec::new());
+        assert!(matches!(join.poll(noop_wake), Poll::Ready(ref v) if v.is_empty()));
+    }
+
+    #[test]
+    fn simple_select_runs_winner_handler() {
+        let mut slow = CountDown { polls_left: 3, value: 1 };
+        let mut fast = CountDown { polls_left: 1, value: 20 };
+        let mut handled = Vec::new();
+        let mut result = Poll::Pending;
+        while let Poll::Pending = result {
+            result = simple_select! {
+                noop_wake,
+                slow => |x| { handled.push("slow"); x },
+                fast => |y| { handled.push("fast"); y + 1 },
+            };
+        }
+        assert!(matches!(result, Poll::Ready(21)));
+        assert_eq!(handled, vec!["fast"]);
+    }
+
+    #[test]
+    fn race_first_ready_wins() {
+        let mut futures = vec![
+            Some(CountDown { polls_left: 2, value: 1 }),
+            Some(CountDown { polls_left: 1, value: 2 }),
+            None,
+            Some(CountDown { polls_left: 1, value: 3 }),
+        ];
+        assert!(matches!(race(&mut futures, noop_wake), Poll::Pending));
+        // 第二个和第四个同时就绪，下标小的第二个赢
+        assert!(matches!(race(&mut futures, noop_wake), Poll::Ready(2)));
+        assert!(futures.is_empty());
+    }
+}
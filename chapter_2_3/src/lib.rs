@@ -1,8 +1,40 @@
-#[derive(Debug)]
+//关掉默认的std feature就是no_std，只依赖core和alloc（Box、Vec、String都在alloc里）。
+//用到HashMap和channel的方法只有开着std时才有。跑测试时总是有std
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::alloc::Layout;
+use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+//core::error要到1.81才稳定，所以只在no_std时用它，开着std时用std::error
+#[cfg(not(feature = "std"))]
+use core::error::Error;
+use core::fmt::{self, Debug, Display};
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::error::Error;
+use core::cmp::Ordering;
+use core::iter::Peekable;
+use core::mem::MaybeUninit;
+use core::ops::Add;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::mpsc;
+
 pub struct List<T> {
     head: Link<T>,
+    len: usize,
 }
 
+///始终保持升序的链表，链表头就是最小值，可以当一个简单的优先队列用
+#[derive(Debug)]
+pub struct SortedList<T: Ord> (List<T>);
+
 ///使用type关键字声明类型的别名。
 type Link<T> = Option<Box<Node<T>>>;
 
@@ -20,11 +52,95 @@ pub struct Iter<'a, T> (Option<&'a Node<T>>);
 
 pub struct IterMut<'a, T> (Option<&'a mut Node<T>>);
 
+///链表某个节点之后部分的只读视图，直接借用原来的节点，不克隆
+pub struct ListView<'a, T> (Option<&'a Node<T>>);
+
+///惰性地合并两个有序链表的Iter，不修改任何一个链表
+pub struct MergeIter<'a, T> (Peekable<Iter<'a, T>>, Peekable<Iter<'a, T>>);
+
+///只读的游标，可以只看当前元素不往前走，适合写解析器
+pub struct Cursor<'a, T> {
+    cur: Option<&'a Node<T>>,
+    pos: usize,
+}
+
+///try_get越界时的错误，带上访问的下标和当时的长度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexError {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "index out of bounds: the len is {} but the index is {}", self.len, self.index)
+    }
+}
+
+impl Error for IndexError {}
+
+///关联列表List<(K, V)>里某个键的位置，用法和HashMap::entry一样
+pub enum Entry<'a, K, V> {
+    ///键已经在链表里了，拿着它的值
+    Occupied(&'a mut V),
+    ///键还不在链表里，插入时放到链表头
+    Vacant(&'a mut List<(K, V)>, K),
+}
+
+///merge_k里放进堆的节点，第二个字段是它来自第几个链表。
+///BinaryHeap是大顶堆，所以比较时反过来，元素相等时下标小的先出来
+struct HeapNode<T>(Box<Node<T>>, usize);
+
+impl<T: Ord> Ord for HeapNode<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.elem.cmp(&self.0.elem).then(other.1.cmp(&self.1))
+    }
+}
+
+impl<T: Ord> PartialOrd for HeapNode<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> PartialEq for HeapNode<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Ord> Eq for HeapNode<T> {}
+
+///snapshot保存下来的链表副本，只能用restore放回去
+#[derive(Debug)]
+pub struct Snapshot<T> (List<T>);
+
 impl<T> List<T> {
     pub fn new() -> Self {
         List {
-            head: None
+            head: None,
+            len: 0,
+        }
+    }
+
+    ///只有一个元素的链表
+    pub fn singleton(elem: T) -> Self {
+        List {
+            head: Some(Box::new(Node { elem, next: None })),
+            len: 1,
+        }
+    }
+
+    ///按迭代器的顺序建链表（第一个元素是链表头），用一个指向最后一个next的可变引用往后接
+    fn from_ordered_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        let mut tail = &mut list.head;
+        for elem in iter {
+            let node = tail.insert(Box::new(Node { elem, next: None }));
+            tail = &mut node.next;
+            list.len += 1;
         }
+        list
     }
 
     ///在链表头加一个节点
@@ -34,15 +150,25 @@ impl<T> List<T> {
             next: self.head.take(), //点运算符将执行很多类型转换的魔法：它将执行自动引用、自动去引用和强制转换，直到类型匹配。
         });
         self.head = Some(node);
+        self.len += 1;
     }
     ///弹出链表头节点，并获取其中的值
     pub fn pop(&mut self) -> Option<T> {
         self.head.take().map(|node| {
             self.head = node.next; //这里可以像用Node类型一样用Box<Node>,是因为Box<T>实现了隐式Deref转换
+            self.len -= 1;
             node.elem
         })
     }
 
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     ///查看链表头节点的值，以引用的方式
     pub fn peek(&self) -> Option<&T> {
         self.head.as_ref().map(|node| {
@@ -56,6 +182,723 @@ impl<T> List<T> {
         })
     }
 
+    ///插到第一个比elem大的元素前面，相等的元素保持插入顺序
+    pub fn insert_sorted(&mut self, elem: T) where T: Ord {
+        let mut link = &mut self.head;
+        while matches!(link, Some(node) if node.elem <= elem) {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        let next = link.take();
+        *link = Some(Box::new(Node { elem, next }));
+        self.len += 1;
+    }
+
+    ///把第index个节点摘下来，重新挂到链表头，只改链接不克隆元素。index越界时返回false
+    pub fn move_to_front(&mut self, index: usize) -> bool {
+        if index == 0 {
+            return self.head.is_some();
+        }
+        let mut node = {
+            //先找到第index-1个节点
+            let mut prev = match self.head.as_deref_mut() {
+                Some(node) => node,
+                None => return false,
+            };
+            for _ in 1..index {
+                prev = match prev.next.as_deref_mut() {
+                    Some(node) => node,
+                    None => return false,
+                };
+            }
+            match prev.next.take() {
+                Some(mut node) => {
+                    prev.next = node.next.take(); //跳过被摘下的节点
+                    node
+                }
+                None => return false,
+            }
+        };
+        node.next = self.head.take();
+        self.head = Some(node);
+        true
+    }
+
+    ///消耗链表，只保留满足f的元素，顺序不变
+    pub fn filtered<F: FnMut(&T) -> bool>(self, mut f: F) -> List<T> {
+        List::from_ordered_iter(self.into_iter().filter(|elem| f(elem)))
+    }
+
+    ///消耗链表，把每个元素映射成新值，顺序不变
+    pub fn mapped<U, F: FnMut(T) -> U>(self, f: F) -> List<U> {
+        List::from_ordered_iter(self.into_iter().map(f))
+    }
+
+    ///用传入的随机数生成器打乱链表：先把节点都摘下来放进Vec，Fisher–Yates洗牌后再重新串起来
+    #[cfg(feature = "rand")]
+    pub fn shuffle<R: rand::Rng>(&mut self, rng: &mut R) {
+        let mut nodes = Vec::new();
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+            nodes.push(node);
+        }
+        for i in (1..nodes.len()).rev() {
+            let j = rng.gen_range(0..=i);
+            nodes.swap(i, j);
+        }
+        for mut node in nodes.into_iter().rev() {
+            node.next = self.head.take();
+            self.head = Some(node);
+        }
+    }
+
+    ///游程编码：把连续相等的元素合并成(值, 个数)，顺序不变
+    pub fn rle(self) -> List<(T, usize)> where T: PartialEq {
+        let mut runs: Vec<(T, usize)> = Vec::new();
+        for elem in self.into_iter() {
+            match runs.last_mut() {
+                Some((last, count)) if *last == elem => *count += 1,
+                _ => runs.push((elem, 1)),
+            }
+        }
+        List::from_ordered_iter(runs)
+    }
+
+    ///游程解码：把(值, 个数)展开成平铺的链表，个数为0的对不产生元素
+    pub fn from_rle(pairs: List<(T, usize)>) -> List<T> where T: Clone {
+        List::from_ordered_iter(pairs.into_iter().flat_map(|(elem, count)| {
+            core::iter::repeat(elem).take(count)
+        }))
+    }
+
+    ///不依赖serde，用元素的Display输出一个JSON数组，例如 [1,2,3]。不做任何转义
+    pub fn to_json_array(&self) -> String where T: Display {
+        let mut json = String::from("[");
+        for (i, elem) in self.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str(&elem.to_string());
+        }
+        json.push(']');
+        json
+    }
+
+    ///统计每个元素出现的次数，key是克隆出来的
+    #[cfg(feature = "std")]
+    pub fn counts(&self) -> HashMap<T, usize> where T: Eq + Hash + Clone {
+        let mut counts = HashMap::new();
+        for elem in self.iter() {
+            *counts.entry(elem.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    ///只保留前len个节点，len不小于链表长度时什么都不做
+    pub fn truncate(&mut self, len: usize) {
+        let mut link = &mut self.head;
+        for _ in 0..len {
+            match link {
+                Some(node) => link = &mut node.next,
+                None => return,
+            }
+        }
+        //剩下的节点交给一个临时链表，借它的Drop循环释放，不会递归
+        drop(List { head: link.take(), len: 0 });
+        self.len = len;
+    }
+
+    ///按链表顺序把元素移进一个大小正好的Box<[T]>，容量由len预先分配，不会重新分配内存
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        let mut vec = Vec::with_capacity(self.len);
+        vec.extend(self.into_iter());
+        vec.into_boxed_slice()
+    }
+
+    ///只反转下标i到j(包含j)之间的节点，要求 i <= j < len，否则panic
+    pub fn reverse_between(&mut self, i: usize, j: usize) {
+        assert!(i <= j && j < self.len, "reverse_between: need i <= j < len, got i = {}, j = {}, len = {}", i, j, self.len);
+        let mut link = &mut self.head;
+        for _ in 0..i {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        //把[i, j]这一段逐个摘下来头插，得到反转后的一段
+        let mut rest = link.take();
+        let mut reversed = None;
+        for _ in i..=j {
+            let mut node = rest.unwrap();
+            rest = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        //接回原来的位置，再把反转段的最后一个节点接上剩下的部分
+        *link = reversed;
+        for _ in i..=j {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        *link = rest;
+    }
+
+    ///正着读和倒着读是否一样。单链表没法倒着走，所以先把引用缓存到Vec里再从两头比较
+    pub fn is_palindrome(&self) -> bool where T: PartialEq {
+        let elems: Vec<&T> = self.iter().collect();
+        elems.iter().eq(elems.iter().rev())
+    }
+
+    ///和Option::get_or_insert_with一样：有头节点就返回它的可变引用，没有就用f生成一个放到头部再返回
+    pub fn get_or_insert_front_with<F: FnOnce() -> T>(&mut self, f: F) -> &mut T {
+        if self.head.is_none() {
+            self.push(f());
+        }
+        &mut self.head.as_mut().unwrap().elem
+    }
+
+    ///交替取两个链表的节点：a0, b0, a1, b1, ...，较长链表剩下的部分接在最后。只改链接
+    pub fn interleave(mut self, mut other: List<T>) -> List<T> {
+        let mut list = List::new();
+        list.len = self.len + other.len;
+        let mut a = self.head.take();
+        let mut b = other.head.take();
+        let mut tail = &mut list.head;
+        loop {
+            match a.take() {
+                Some(mut node) => {
+                    a = node.next.take();
+                    tail = &mut tail.insert(node).next;
+                }
+                None => {
+                    *tail = b; //轮到的链表空了，另一个剩下的整段接上
+                    break;
+                }
+            }
+            core::mem::swap(&mut a, &mut b);
+        }
+        list
+    }
+
+    ///按n个一组切开链表：返回所有正好n个元素的块，以及剩下不足n个元素的链表。n为0时panic
+    pub fn chunks_exact(mut self, n: usize) -> (Vec<Vec<T>>, List<T>) {
+        assert!(n != 0, "chunk size must be non-zero");
+        let mut chunks = Vec::with_capacity(self.len / n);
+        while self.len >= n {
+            chunks.push((0..n).map(|_| self.pop().unwrap()).collect());
+        }
+        (chunks, self) //剩下的节点原样留在链表里
+    }
+
+    ///每n个相邻元素求和得到新链表，最后一块可以不足n个。n为0时panic
+    pub fn chunk_sum(&self, n: usize) -> List<T> where T: Copy + Default + Add<Output = T> {
+        assert!(n != 0, "chunk size must be non-zero");
+        let mut iter = self.iter().copied().peekable();
+        List::from_ordered_iter(core::iter::from_fn(|| {
+            iter.peek()?;
+            Some(iter.by_ref().take(n).fold(T::default(), |sum, elem| sum + elem))
+        }))
+    }
+
+    ///带下标的遍历，头节点的下标是0
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.iter().enumerate()
+    }
+
+    ///长度正好是N时转成数组，否则把链表原样放在Err里还回去。不要求T: Default
+    pub fn try_into_array<const N: usize>(mut self) -> Result<[T; N], List<T>> {
+        if self.len != N {
+            return Err(self);
+        }
+        let mut array: MaybeUninit<[T; N]> = MaybeUninit::uninit();
+        let ptr = array.as_mut_ptr() as *mut T;
+        for i in 0..N {
+            //长度已经检查过，pop一定有值；每个位置正好写一次
+            unsafe { ptr.add(i).write(self.pop().unwrap()) };
+        }
+        //N个位置都已经初始化了
+        Ok(unsafe { array.assume_init() })
+    }
+
+    ///按节点折叠，f的第三个参数表示当前是不是最后一个节点(它的next为None)，方便对尾部特殊处理
+    pub fn fold_links<B, F>(&self, init: B, mut f: F) -> B where F: FnMut(B, &T, bool) -> B {
+        let mut acc = init;
+        let mut cur = self.head.as_deref();
+        while let Some(node) = cur {
+            acc = f(acc, &node.elem, node.next.is_none());
+            cur = node.next.as_deref();
+        }
+        acc
+    }
+
+    ///偶数下标(0,2,4...)的节点放进第一个链表，奇数下标的放进第二个，各自保持原来的顺序。只改链接
+    pub fn split_even_odd(mut self) -> (List<T>, List<T>) {
+        let mut evens = List::new();
+        let mut odds = List::new();
+        evens.len = self.len.div_ceil(2);
+        odds.len = self.len / 2;
+        let mut even_tail = &mut evens.head;
+        let mut odd_tail = &mut odds.head;
+        let mut link = self.head.take();
+        let mut is_even = true;
+        while let Some(mut node) = link {
+            link = node.next.take();
+            if is_even {
+                even_tail = &mut even_tail.insert(node).next;
+            } else {
+                odd_tail = &mut odd_tail.insert(node).next;
+            }
+            is_even = !is_even;
+        }
+        (evens, odds)
+    }
+
+    ///每k个相邻节点为一组各自反转，最后不足k个的一组由reverse_remainder决定是否反转。只改链接，k为0时panic
+    pub fn reverse_in_groups(&mut self, k: usize, reverse_remainder: bool) {
+        assert!(k != 0, "group size must be non-zero");
+        let mut remaining = self.len;
+        let mut rest = self.head.take();
+        let mut tail = &mut self.head;
+        while remaining > 0 {
+            let size = remaining.min(k);
+            if size < k && !reverse_remainder {
+                *tail = rest; //最后一组原样接回去
+                break;
+            }
+            //头插法反转这一组
+            let mut group = None;
+            for _ in 0..size {
+                let mut node = rest.unwrap();
+                rest = node.next.take();
+                node.next = group;
+                group = Some(node);
+            }
+            *tail = group;
+            for _ in 0..size {
+                tail = &mut tail.as_mut().unwrap().next;
+            }
+            remaining -= size;
+        }
+    }
+
+    ///快慢指针找中间元素：快指针一次走两步，慢指针一次走一步，只遍历一遍。
+    ///长度为偶数时返回靠后的那个中间元素，例如[1,2,3,4]返回3
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head.as_deref()?;
+        let mut fast = self.head.as_deref();
+        while let Some(next) = fast.and_then(|node| node.next.as_deref()) {
+            slow = slow.next.as_deref().unwrap(); //快指针能走两步，慢指针一定能走一步
+            fast = next.next.as_deref();
+        }
+        Some(&slow.elem)
+    }
+
+    ///Floyd判圈(龟兔赛跑)：快指针一次两步，慢指针一次一步，如果两者指向同一个节点就说明有环。
+    ///每个节点都被唯一的Box拥有，通过安全代码构造出来的链表不可能有环，所以这里总是返回false，
+    ///它只用来检查通过unsafe手段拼出来的链表
+    pub fn has_cycle(&self) -> bool {
+        let mut slow = self.head.as_deref();
+        let mut fast = self.head.as_deref();
+        while let Some(next) = fast.and_then(|node| node.next.as_deref()) {
+            slow = slow.and_then(|node| node.next.as_deref());
+            fast = next.next.as_deref();
+            if let (Some(a), Some(b)) = (slow, fast) {
+                if core::ptr::eq(a, b) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    ///倒数第n个元素(0是最后一个)。前指针先走n步，然后两个指针一起走，前指针到尾时后指针就是答案，只遍历一遍
+    pub fn nth_from_end(&self, n: usize) -> Option<&T> {
+        let mut lead = self.head.as_deref()?;
+        for _ in 0..n {
+            lead = lead.next.as_deref()?; //链表不够长
+        }
+        let mut trail = self.head.as_deref()?;
+        while let Some(next) = lead.next.as_deref() {
+            lead = next;
+            trail = trail.next.as_deref()?;
+        }
+        Some(&trail.elem)
+    }
+
+    ///保存一份当前链表的深拷贝，改坏了可以用restore回滚
+    pub fn snapshot(&self) -> Snapshot<T> where T: Clone {
+        Snapshot(self.clone())
+    }
+
+    ///用快照替换掉当前的内容，原来的节点会被drop
+    pub fn restore(&mut self, snap: Snapshot<T>) {
+        *self = snap.0;
+    }
+
+    ///在每两个相邻元素之间插入一个sep的克隆，会消耗原链表
+    pub fn intersperse(self, sep: T) -> List<T> where T: Clone {
+        let mut first = true;
+        List::from_ordered_iter(self.into_iter().flat_map(|elem| {
+            let before = if first { None } else { Some(sep.clone()) };
+            first = false;
+            before.into_iter().chain(Some(elem))
+        }))
+    }
+
+    ///从链表头开始的只读游标
+    pub fn cursor(&self) -> Cursor<'_, T> {
+        Cursor { cur: self.head.as_deref(), pos: 0 }
+    }
+
+    ///和push一样，但分配节点失败时不abort，把元素放在Err里还回来。
+    ///Box::try_new还没稳定，这里直接用alloc::alloc手动分配，返回空指针就是失败。
+    ///想在测试里模拟内存不足要靠平台相关的手段（比如限制进程内存），所以只测了成功的情况
+    pub fn try_push(&mut self, elem: T) -> Result<(), T> {
+        let layout = Layout::new::<Node<T>>(); //Node里总有一个next指针，大小不会是0
+        let ptr = unsafe { alloc::alloc::alloc(layout) } as *mut Node<T>;
+        if ptr.is_null() {
+            return Err(elem);
+        }
+        unsafe {
+            ptr.write(Node { elem, next: self.head.take() });
+            self.head = Some(Box::from_raw(ptr)); //布局和Box::new分配的一样，可以交给Box释放
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    ///把iter里的元素按顺序接到链表尾部，返回&mut Self可以链式调用，用来重复利用同一个链表
+    pub fn collect_into<I: IntoIterator<Item = T>>(&mut self, iter: I) -> &mut Self {
+        let mut tail = &mut self.head;
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        for elem in iter {
+            let node = tail.insert(Box::new(Node { elem, next: None }));
+            tail = &mut node.next;
+            self.len += 1;
+        }
+        self
+    }
+
+    ///按值遍历，bool只在最后一个元素上为true。链表记着长度，不需要往前多看一个
+    pub fn into_iter_marked(self) -> impl Iterator<Item = (T, bool)> {
+        let mut left = self.len;
+        self.into_iter().map(move |elem| {
+            left -= 1;
+            (elem, left == 0)
+        })
+    }
+
+    ///第i个元素的引用，越界时返回带下标和长度的IndexError
+    pub fn try_get(&self, i: usize) -> Result<&T, IndexError> {
+        self.iter().nth(i).ok_or(IndexError { index: i, len: self.len })
+    }
+
+    ///去掉所有重复元素（保留第一次出现的），返回每个值原来出现了几次
+    #[cfg(feature = "std")]
+    pub fn dedup_all_counted(&mut self) -> HashMap<T, usize> where T: Eq + Hash + Clone {
+        let mut counts = HashMap::new();
+        let old = core::mem::replace(self, List::new());
+        *self = List::from_ordered_iter(old.into_iter().filter(|elem| {
+            let count = counts.entry(elem.clone()).or_insert(0);
+            *count += 1;
+            *count == 1
+        }));
+        counts
+    }
+
+    ///按顺序把元素一个个发到channel里，接收端已经关掉时停下，把没送出去的那个元素放在错误里返回
+    #[cfg(feature = "std")]
+    pub fn send_all(self, tx: &mpsc::Sender<T>) -> Result<(), mpsc::SendError<T>> {
+        for elem in self.into_iter() {
+            tx.send(elem)?;
+        }
+        Ok(())
+    }
+
+    ///把other的节点整体接到链表头前面，other变成空链表。只需要走到other的尾部，和self的长度无关
+    pub fn prepend(&mut self, other: &mut List<T>) {
+        let mut tail = &mut other.head;
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        *tail = self.head.take();
+        self.head = other.head.take();
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    ///没有初始值的fold：用第一个元素当累加器，空链表返回None
+    pub fn reduce<F: FnMut(T, T) -> T>(self, f: F) -> Option<T> {
+        self.into_iter().reduce(f)
+    }
+
+    ///和Iterator::scan一样带一个可变状态往后走，f返回None时停止，不消耗链表
+    pub fn scan_iter<'a, St: 'a, B, F>(&'a self, init: St, f: F) -> impl Iterator<Item = B> + 'a
+        where F: FnMut(&mut St, &T) -> Option<B> + 'a
+    {
+        self.iter().scan(init, f)
+    }
+
+    ///最大元素的下标，有多个最大值时取最靠前的，空链表返回None。
+    ///Iterator::max_by_key遇到相等的会取最后一个，所以这里自己比较
+    pub fn position_max(&self) -> Option<usize> where T: Ord {
+        self.position_by(|elem, best| elem > best)
+    }
+
+    ///最小元素的下标，有多个最小值时取最靠前的
+    pub fn position_min(&self) -> Option<usize> where T: Ord {
+        self.position_by(|elem, best| elem < best)
+    }
+
+    ///better(elem, best)为true时才换成新的下标，所以相等时保留前面的
+    fn position_by<F: Fn(&T, &T) -> bool>(&self, better: F) -> Option<usize> {
+        let mut iter = self.iter().enumerate();
+        let (mut best_index, mut best) = iter.next()?;
+        for (index, elem) in iter {
+            if better(elem, best) {
+                best_index = index;
+                best = elem;
+            }
+        }
+        Some(best_index)
+    }
+
+    ///只保留indices里那些位置上的元素，其余的drop掉。indices要求升序，重复的和越界的下标会被忽略
+    pub fn keep_indices(&mut self, indices: &[usize]) {
+        let mut wanted = indices.iter().copied().peekable();
+        let old = core::mem::replace(self, List::new());
+        *self = List::from_ordered_iter(old.into_iter().enumerate().filter_map(|(i, elem)| {
+            while wanted.next_if(|&index| index < i).is_some() {}
+            wanted.next_if_eq(&i).map(|_| elem)
+        }));
+    }
+
+    ///按顺序把元素移进Vec再排序，Vec的容量按len一次分配好
+    pub fn into_sorted_vec(self) -> Vec<T> where T: Ord {
+        let mut vec = Vec::with_capacity(self.len);
+        vec.extend(self.into_iter());
+        vec.sort();
+        vec
+    }
+
+    ///假设链表已经按pred分好（前面都是true，后面都是false），返回第一个让pred为false的下标。
+    ///单链表不能随机访问，没法二分，只能从头扫一遍
+    pub fn partition_point<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+        self.iter().take_while(|elem| pred(elem)).count()
+    }
+
+    ///和retain一样，但f还会拿到元素在删除之前的原始下标
+    pub fn retain_indexed<F: FnMut(usize, &T) -> bool>(&mut self, mut f: F) {
+        let mut link = &mut self.head;
+        let mut index = 0;
+        while let Some(node) = link {
+            if f(index, &node.elem) {
+                link = &mut link.as_mut().unwrap().next;
+            } else {
+                let node = link.take().unwrap();
+                *link = node.next; //跳过这个节点，它在这里被drop
+                self.len -= 1;
+            }
+            index += 1;
+        }
+    }
+
+    ///f(上一个保留下来的, 当前, 原链表里的下一个)返回false的元素会被删掉。
+    ///决定完下一个之前，上一个保留的元素先拿在手里不接进新链表，这样不需要同时借用新链表的尾部
+    pub fn retain_neighbors<F>(&mut self, mut f: F) where F: FnMut(Option<&T>, &T, Option<&T>) -> bool {
+        let old = core::mem::replace(self, List::new());
+        let mut iter = old.into_iter().peekable();
+        let mut tail = &mut self.head;
+        let mut last_kept: Option<T> = None;
+        let mut len = 0;
+        while let Some(elem) = iter.next() {
+            if f(last_kept.as_ref(), &elem, iter.peek()) {
+                if let Some(prev) = last_kept.replace(elem) {
+                    let node = tail.insert(Box::new(Node { elem: prev, next: None }));
+                    tail = &mut node.next;
+                    len += 1;
+                }
+            }
+        }
+        if let Some(prev) = last_kept {
+            *tail = Some(Box::new(Node { elem: prev, next: None }));
+            len += 1;
+        }
+        self.len = len;
+    }
+
+    ///前两个元素，解析时常用的两个token前瞻
+    pub fn peek_two(&self) -> (Option<&T>, Option<&T>) {
+        let first = self.head.as_deref();
+        let second = first.and_then(|node| node.next.as_deref());
+        (first.map(|node| &node.elem), second.map(|node| &node.elem))
+    }
+
+    ///对每个元素调用f，把返回的所有元素按顺序接成一个新链表，从头往尾建
+    pub fn flat_map<U, I, F>(self, f: F) -> List<U>
+        where I: IntoIterator<Item = U>, F: FnMut(T) -> I
+    {
+        List::from_ordered_iter(self.into_iter().flat_map(f))
+    }
+
+    ///从尾往头折叠：f(第一个, f(第二个, ... f(最后一个, init)))。
+    ///单链表没法倒着走，先把所有元素的引用收集到Vec里再倒序折叠，额外用O(n)的空间
+    pub fn fold_right<B, F: FnMut(&T, B) -> B>(&self, init: B, mut f: F) -> B {
+        let elems: Vec<&T> = self.iter().collect();
+        elems.into_iter().rev().fold(init, |acc, elem| f(elem, acc))
+    }
+
+    ///和Vec::dedup_by一样合并连续的相等元素：same(当前元素, 上一个保留的元素)返回true时删掉当前元素
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same: F) {
+        let mut cur = match self.head.as_deref_mut() {
+            Some(node) => node,
+            None => return,
+        };
+        while let Some(mut next) = cur.next.take() {
+            if same(&mut next.elem, &mut cur.elem) {
+                cur.next = next.next.take(); //next在这里被drop
+                self.len -= 1;
+            } else {
+                cur = cur.next.insert(next);
+            }
+        }
+    }
+
+    ///key相同的连续元素只保留第一个
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    ///向左旋转n步：前n个元素按原顺序挪到尾部，n会先对长度取模
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        let n = n % self.len;
+        if n == 0 {
+            return;
+        }
+        let mut cut = self.head.as_deref_mut().unwrap();
+        for _ in 1..n {
+            cut = cut.next.as_deref_mut().unwrap();
+        }
+        let mut rest = cut.next.take(); //n < len，后半段一定不为空
+        let front = self.head.take();
+        let mut tail = &mut rest;
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        *tail = front;
+        self.head = rest;
+    }
+
+    ///旋转链表，让第一个等于target的元素成为链表头，找不到时返回false，链表不变
+    pub fn rotate_to(&mut self, target: &T) -> bool where T: PartialEq {
+        match self.iter().position(|elem| elem == target) {
+            Some(index) => {
+                self.rotate_left(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    ///合并k个有序链表。堆里放每个链表当前的头节点，每次取出最小的接到结果尾部，
+    ///再把它原来的下一个节点放回堆里。节点直接重新链接，不重新分配，O(N log k)
+    pub fn merge_k(lists: Vec<List<T>>) -> List<T> where T: Ord {
+        let mut heap = BinaryHeap::with_capacity(lists.len());
+        let mut result = List::new();
+        for (index, mut list) in lists.into_iter().enumerate() {
+            result.len += list.len;
+            list.len = 0;
+            if let Some(node) = list.head.take() {
+                heap.push(HeapNode(node, index));
+            }
+        }
+        let mut tail = &mut result.head;
+        while let Some(HeapNode(mut node, index)) = heap.pop() {
+            if let Some(next) = node.next.take() {
+                heap.push(HeapNode(next, index));
+            }
+            tail = &mut tail.insert(node).next;
+        }
+        result
+    }
+
+    ///从链表头开始每隔step个取一个，中间的节点直接跳过。step为0时panic
+    pub fn iter_step(&self, step: usize) -> impl Iterator<Item = &T> {
+        assert!(step != 0, "step must be non-zero");
+        self.iter().step_by(step)
+    }
+
+    //问题：能不能在List里缓存一个指向尾节点的裸指针，让last()变成O(1)？
+    //答：节点是Box<Node>，Box和&mut一样要求独占。push、pop、各种重新链接的操作都会移动或可变借用这些Box，
+    //   按Rust的别名规则（Stacked Borrows），之前从Box里取出的裸指针在这之后就失效了，再读就是未定义行为，
+    //   miri会直接报错。要安全地缓存尾指针，整个链表都得改成用裸指针管理节点（too-many-lists第五章的做法），
+    //   而不是Box。所以这里的last()老老实实从头走到尾，是O(n)的
+
+    ///最后一个元素，空链表返回None
+    pub fn last(&self) -> Option<&T> {
+        let mut node = self.head.as_deref()?;
+        while let Some(next) = node.next.as_deref() {
+            node = next;
+        }
+        Some(&node.elem)
+    }
+
+    ///倒序的克隆，原链表不变。push本来就是往头部加，所以从头往后遍历着push就是倒序
+    pub fn reversed(&self) -> List<T> where T: Clone {
+        let mut list = List::new();
+        for elem in self.iter() {
+            list.push(elem.clone());
+        }
+        list
+    }
+
+    ///从头到尾对每个元素调用f，原地修改
+    pub fn for_each_mut<F: FnMut(&mut T)>(&mut self, f: F) {
+        self.iter_mut().for_each(f);
+    }
+
+    ///只留下前n个元素，其余的释放掉
+    pub fn take(mut self, n: usize) -> List<T> {
+        self.truncate(n);
+        self
+    }
+
+    ///丢掉前n个元素，剩下的原样留着，不重新建节点
+    pub fn skip(mut self, n: usize) -> List<T> {
+        for _ in 0..n {
+            if self.pop().is_none() {
+                break;
+            }
+        }
+        self
+    }
+
+    ///和itertools的coalesce一样：f(当前, 下一个)返回Ok就把合并后的值当作当前值继续往后合并，
+    ///返回Err((a, b))就输出a，再从b开始。元素都是按值移动，不会克隆
+    pub fn coalesce<F: FnMut(T, T) -> Result<T, (T, T)>>(self, mut f: F) -> List<T> {
+        let mut iter = self.into_iter();
+        let mut cur = iter.next();
+        List::from_ordered_iter(core::iter::from_fn(move || {
+            let mut acc = cur.take()?;
+            for next in iter.by_ref() {
+                match f(acc, next) {
+                    Ok(merged) => acc = merged,
+                    Err((first, second)) => {
+                        cur = Some(second);
+                        return Some(first);
+                    }
+                }
+            }
+            Some(acc)
+        }))
+    }
+
+    ///对每一对相邻元素调用f，结果组成一个长度为len-1的新链表，少于两个元素时返回空链表
+    pub fn adjacent_map<U, F: FnMut(&T, &T) -> U>(&self, mut f: F) -> List<U> {
+        List::from_ordered_iter(self.iter().zip(self.iter().skip(1)).map(|(a, b)| f(a, b)))
+    }
+
     ///into_iter会夺走所有权
     pub fn into_iter(self) -> IntoIter<T> {
         IntoIter(self) //元组结构体方式的new函数
@@ -64,12 +907,25 @@ impl<T> List<T> {
     pub fn iter(&self) -> Iter<T> {
         Iter(self.head.as_deref())
     }
+    ///头节点之后的所有元素组成的视图，空链表返回None
+    pub fn tail(&self) -> Option<ListView<'_, T>> {
+        self.head.as_deref().map(|node| ListView(node.next.as_deref()))
+    }
+
     ///iter_mut是可变借用
     pub fn iter_mut(&mut self) -> IterMut<T> {
         IterMut(self.head.as_deref_mut())
     }
+
+    ///按顺序交替取出两个有序链表中较小的元素的引用，相等时先取a的
+    pub fn merge_iter<'a>(a: &'a List<T>, b: &'a List<T>) -> MergeIter<'a, T>
+        where T: Ord
+    {
+        MergeIter(a.iter().peekable(), b.iter().peekable())
+    }
 }
 
+//遍历整个链表的操作（drop、clone、eq、Debug）都写成循环，不能沿着Box<Node>递归，否则链表很长时会爆栈
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
         let mut link = self.head.take();
@@ -79,42 +935,357 @@ impl<T> Drop for List<T> {
     }
 }
 
-impl<T> Iterator for IntoIter<T> {
-    type Item = T;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.pop()
+impl<'a, T> ListView<'a, T> {
+    ///再往后截掉一个元素，视图为空时返回None
+    pub fn tail(&self) -> Option<ListView<'a, T>> {
+        self.0.map(|node| ListView(node.next.as_deref()))
     }
-}
 
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.map(|node| { //self.0的类型是是Option<&>:
-            // & is copy, Option<&> is also Copy. So when we did self.0.map
-            // it was fine because the Option was just copied.
-            self.0 = node.next.as_deref();
-            &node.elem
-        })
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter(self.0)
     }
-}
 
-impl<'a, T> Iterator for IterMut<'a, T> {
-    type Item = &'a mut T;
-    fn next(&mut self) -> Option<Self::Item> {
-        self.0.take().map(|node| { //&mut isn't Copy (if you copied an &mut,
-            // you'd have two &mut's to the same location in memory, which is forbidden).
-            // Instead, we should properly take the Option to get it.
-            self.0 = node.next.as_deref_mut();
-            &mut node.elem
-        })
+    ///需要遍历一遍，O(n)
+    pub fn len(&self) -> usize {
+        self.iter().count()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::List;
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
 
-    #[test]
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        self.iter().nth(index)
+    }
+}
+
+impl<'a, T> Cursor<'a, T> {
+    ///当前元素，不移动游标，走到末尾后返回None
+    pub fn peek(&self) -> Option<&'a T> {
+        self.cur.map(|node| &node.elem)
+    }
+
+    ///往后走一步，已经在末尾时什么都不做
+    pub fn advance(&mut self) {
+        if let Some(node) = self.cur {
+            self.cur = node.next.as_deref();
+            self.pos += 1;
+        }
+    }
+
+    ///已经走过的元素个数
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+///把List<(K, V)>当作关联列表(association list)来用
+impl<K, V> List<(K, V)> {
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    ///从头找key对应的项。先用不可变借用找到下标再可变借用，
+    ///直接在iter_mut里找到就返回的话，借用检查器会认为后面创建Vacant时self还被借着
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> where K: PartialEq {
+        let index = self.keys().position(|k| *k == key);
+        match index {
+            Some(index) => Entry::Occupied(&mut self.iter_mut().nth(index).unwrap().1),
+            None => Entry::Vacant(self, key),
+        }
+    }
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    ///已经有值就返回它，没有时把(key, default)插到链表头
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(value) => value,
+            Entry::Vacant(list, key) => {
+                list.push((key, f()));
+                &mut list.peek_mut().unwrap().1
+            }
+        }
+    }
+}
+
+impl<T: Ord> SortedList<T> {
+    pub fn new() -> Self {
+        SortedList(List::new())
+    }
+
+    pub fn push(&mut self, elem: T) {
+        self.0.insert_sorted(elem);
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.0.peek()
+    }
+}
+
+impl<T: Ord> Default for SortedList<T> {
+    fn default() -> Self {
+        SortedList::new()
+    }
+}
+
+//不用derive：derive出来的Debug同样会沿着Box<Node>递归，这里直接按顺序打印元素
+impl<T: Debug> Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+//和Vec一样，extend把元素按顺序接到尾部
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.collect_into(iter);
+    }
+}
+
+//T是Copy时可以直接从借用的迭代器里复制元素，比如list.extend(slice.iter())
+impl<'a, T: Copy + 'a> Extend<&'a T> for List<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.collect_into(iter.into_iter().copied());
+    }
+}
+
+//不用derive：derive出来的clone会沿着Box<Node>递归，链表很长时会爆栈
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        List::from_ordered_iter(self.iter().cloned())
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+//advance_by：最多前进n个元素，提前耗尽时返回Err(还差几个)，和nightly标准库的签名一致
+impl<T> IntoIter<T> {
+    pub fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        for i in 0..n {
+            if self.0.pop().is_none() {
+                return Err(n - i);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        for i in 0..n {
+            match self.0 {
+                Some(node) => self.0 = node.next.as_deref(),
+                None => return Err(n - i),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        for i in 0..n {
+            match self.0.take() {
+                Some(node) => self.0 = node.next.as_deref_mut(),
+                None => return Err(n - i),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.map(|node| { //self.0的类型是是Option<&>:
+            // & is copy, Option<&> is also Copy. So when we did self.0.map
+            // it was fine because the Option was just copied.
+            self.0 = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.take().map(|node| { //&mut isn't Copy (if you copied an &mut,
+            // you'd have two &mut's to the same location in memory, which is forbidden).
+            // Instead, we should properly take the Option to get it.
+            self.0 = node.next.as_deref_mut();
+            &mut node.elem
+        })
+    }
+}
+
+impl<'a, T: Ord> Iterator for MergeIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.0.peek(), self.1.peek()) {
+            (Some(a), Some(b)) if b < a => self.1.next(),
+            (Some(_), _) => self.0.next(),
+            (None, _) => self.1.next(),
+        }
+    }
+}
+
+///容量固定为N的单链表，节点放在数组里，用下标代替Box指针，push和pop都不会分配内存。
+///只在链表头增删，所以最后push的节点总是在数组的第len-1个位置，下一个空位永远是第len个
+pub struct StackList<T, const N: usize> {
+    nodes: [MaybeUninit<StackNode<T>>; N],
+    head: Option<usize>,
+    len: usize,
+}
+
+struct StackNode<T> {
+    elem: T,
+    next: Option<usize>,
+}
+
+pub struct StackIter<'a, T, const N: usize> {
+    list: &'a StackList<T, N>,
+    cur: Option<usize>,
+}
+
+impl<T, const N: usize> StackList<T, N> {
+    pub fn new() -> Self {
+        StackList {
+            nodes: [const { MaybeUninit::uninit() }; N],
+            head: None,
+            len: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///在链表头加一个节点，满了的话把元素放在Err里还回来
+    pub fn push(&mut self, elem: T) -> Result<(), T> {
+        if self.len == N {
+            return Err(elem);
+        }
+        self.nodes[self.len].write(StackNode { elem, next: self.head });
+        self.head = Some(self.len);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let index = self.head?;
+        //head指向的位置一定初始化过，读出来以后这个位置就当作空位
+        let node = unsafe { self.nodes[index].assume_init_read() };
+        self.head = node.next;
+        self.len -= 1;
+        Some(node.elem)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.map(|index| unsafe { &self.nodes[index].assume_init_ref().elem })
+    }
+
+    pub fn iter(&self) -> StackIter<'_, T, N> {
+        StackIter { list: self, cur: self.head }
+    }
+}
+
+//MaybeUninit不会自动drop里面的值，要手动把还在链表里的元素都pop掉
+impl<T, const N: usize> Drop for StackList<T, N> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+//除了元素，还打印长度和容量，方便看还剩多少空位
+impl<T: Debug, const N: usize> Debug for StackList<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StackList")
+            .field("len", &self.len)
+            .field("capacity", &N)
+            .field("elems", &self.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<T, const N: usize> Default for StackList<T, N> {
+    fn default() -> Self {
+        StackList::new()
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for StackIter<'a, T, N> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cur.map(|index| {
+            //能从链表头沿着next走到的位置都是初始化过的
+            let node = unsafe { self.list.nodes[index].assume_init_ref() };
+            self.cur = node.next;
+            &node.elem
+        })
+    }
+}
+
+//只在no_std下编译，确认链表的核心接口不依赖std：cargo build --no-default-features
+#[cfg(not(feature = "std"))]
+#[allow(dead_code)]
+mod no_std_check {
+    use super::{List, StackList};
+    use alloc::vec::Vec;
+
+    fn core_api() -> Vec<i32> {
+        let mut list = List::new();
+        list.push(2);
+        list.push(1);
+        let _ = list.try_push(0);
+        let mut stack: StackList<i32, 2> = StackList::new();
+        let _ = stack.push(3);
+        list.clone().into_iter().chain(stack.iter().copied()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Entry, IndexError, List, StackList};
+    use std::error::Error;
+
+    ///按迭代顺序建链表，省得每个测试都倒着push一遍
+    fn list_of<T>(elems: impl IntoIterator<Item = T>) -> List<T> {
+        List::from_ordered_iter(elems)
+    }
+
+    #[test]
     fn into_iter() {
         let mut list = List::new();
         list.push(1);
@@ -159,4 +1330,1029 @@ mod tests {
         assert_eq!(iter_mut.next(), Some(&mut 1));
         assert_eq!(iter_mut.next(), None);
     }
+
+    #[test]
+    fn merge_iter() {
+        let mut a = List::new();
+        a.push(5);
+        a.push(3);
+        a.push(1);
+        let mut b = List::new();
+        b.push(4);
+        b.push(2);
+
+        let merged: Vec<&i32> = List::merge_iter(&a, &b).collect();
+        assert_eq!(merged, vec![&1, &2, &3, &4, &5]);
+
+        //两个链表都没有被修改
+        assert_eq!(a.peek(), Some(&1));
+        assert_eq!(b.peek(), Some(&2));
+
+        let empty = List::new();
+        let merged: Vec<&i32> = List::merge_iter(&empty, &b).collect();
+        assert_eq!(merged, vec![&2, &4]);
+    }
+
+    #[test]
+    fn move_to_front() {
+        let mut list = List::new();
+        list.push(4);
+        list.push(3);
+        list.push(2);
+        list.push(1);
+
+        assert!(list.move_to_front(2)); //中间的节点
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 1, 2, 4]);
+
+        assert!(list.move_to_front(3)); //尾节点
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 3, 1, 2]);
+
+        assert!(list.move_to_front(0));
+        assert!(!list.move_to_front(4));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 3, 1, 2]);
+
+        let mut empty: List<i32> = List::new();
+        assert!(!empty.move_to_front(0));
+    }
+
+    #[test]
+    fn tail() {
+        assert!(List::<i32>::new().tail().is_none());
+        let list = list_of(1..=5);
+
+        let view = list.tail().unwrap().tail().unwrap();
+        assert_eq!(view.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(view.len(), 3);
+        assert_eq!(view.get(0), Some(&3));
+        assert_eq!(view.get(2), Some(&5));
+        assert_eq!(view.get(3), None);
+
+        let last = view.tail().unwrap().tail().unwrap();
+        assert_eq!(last.iter().collect::<Vec<_>>(), vec![&5]);
+        let empty = last.tail().unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.tail().is_none());
+    }
+
+    #[test]
+    fn insert_sorted() {
+        let mut list = List::new();
+        for i in [3, 1, 4, 1, 5] {
+            list.insert_sorted(i);
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn sorted_list() {
+        use super::SortedList;
+
+        let mut queue = SortedList::new();
+        assert_eq!(queue.peek_min(), None);
+        for i in [5, 2, 8, 1, 9, 2] {
+            queue.push(i);
+        }
+        assert_eq!(queue.peek_min(), Some(&1));
+
+        let mut popped = Vec::new();
+        while let Some(min) = queue.pop_min() {
+            popped.push(min);
+        }
+        assert_eq!(popped, vec![1, 2, 2, 5, 8, 9]);
+        assert_eq!(queue.pop_min(), None);
+    }
+
+    #[test]
+    fn keys_values() {
+        let mut list = List::new();
+        list.push(("c", 3));
+        list.push(("b", 2));
+        list.push(("a", 1));
+
+        assert_eq!(list.keys().copied().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(list.values().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn to_json_array() {
+        let mut list = List::new();
+        assert_eq!(list.to_json_array(), "[]");
+        list.push(3);
+        list.push(2);
+        list.push(1);
+        assert_eq!(list.to_json_array(), "[1,2,3]");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn counts() {
+        let mut list = List::new();
+        for c in ['c', 'a', 'a', 'b', 'a'] {
+            list.push(c);
+        }
+        let counts = list.counts();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&'a'], 3);
+        assert_eq!(counts[&'b'], 1);
+        assert_eq!(counts[&'c'], 1);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut list = list_of(1..=5);
+        list.truncate(3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(list.len(), 3);
+
+        let mut list = list_of(1..=5);
+        list.truncate(10);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.len(), 5);
+
+        let mut list = list_of(1..=5);
+        list.truncate(0);
+        assert_eq!(list.peek(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn filtered_mapped() {
+        let list = list_of(1..=6);
+
+        let evens = list.filtered(|&x| x % 2 == 0);
+        assert_eq!(evens.iter().copied().collect::<Vec<_>>(), vec![2, 4, 6]);
+
+        let strings = evens.mapped(|x| x.to_string());
+        assert_eq!(strings.iter().map(|s| s.as_str()).collect::<Vec<_>>(), vec!["2", "4", "6"]);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn shuffle() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut a = list_of((0..10).rev());
+        let mut b = list_of((0..10).rev());
+        a.shuffle(&mut StdRng::seed_from_u64(42));
+        b.shuffle(&mut StdRng::seed_from_u64(42));
+        let a: Vec<i32> = a.into_iter().collect();
+        let b: Vec<i32> = b.into_iter().collect();
+        assert_eq!(a, b); //同一个种子，结果相同
+
+        let mut sorted = a.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>()); //元素一个不少
+        assert_ne!(a, (0..10).rev().collect::<Vec<_>>());
+
+        let mut empty: List<i32> = List::new();
+        empty.shuffle(&mut StdRng::seed_from_u64(42));
+        assert_eq!(empty.peek(), None);
+    }
+
+    #[test]
+    fn rle() {
+        let mut list = List::new();
+        for c in ['c', 'c', 'c', 'b', 'a', 'a'] {
+            list.push(c);
+        }
+        let runs: Vec<(char, usize)> = list.rle().into_iter().collect();
+        assert_eq!(runs, vec![('a', 2), ('b', 1), ('c', 3)]);
+
+        assert_eq!(List::<char>::new().rle().peek(), None);
+    }
+
+    #[test]
+    fn from_rle() {
+        let mut list = List::new();
+        for c in ['c', 'c', 'c', 'b', 'a', 'a'] {
+            list.push(c);
+        }
+        assert_eq!(List::from_rle(list.clone().rle()), list);
+
+        let mut pairs = List::new();
+        pairs.push(('y', 2));
+        pairs.push(('x', 0));
+        let decoded: Vec<char> = List::from_rle(pairs).into_iter().collect();
+        assert_eq!(decoded, vec!['y', 'y']);
+    }
+
+    #[test]
+    fn len() {
+        let mut list = List::new();
+        assert!(list.is_empty());
+        list.push(1);
+        list.push(2);
+        list.insert_sorted(3);
+        assert_eq!(list.len(), 3);
+        list.pop();
+        assert_eq!(list.len(), 2);
+
+        let mapped = list.mapped(|x| x * 10);
+        assert_eq!(mapped.len(), 2);
+    }
+
+    #[test]
+    fn into_boxed_slice() {
+        let mut list = List::new();
+        list.push(3);
+        list.push(2);
+        list.push(1);
+        let slice = list.into_boxed_slice();
+        assert_eq!(slice.len(), 3);
+        assert_eq!(&*slice, &[1, 2, 3]);
+
+        assert!(List::<i32>::new().into_boxed_slice().is_empty());
+    }
+
+    #[test]
+    fn advance_by() {
+        let mut list = list_of(1..=5);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.advance_by(2), Ok(()));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.advance_by(5), Err(3));
+        assert_eq!(iter.next(), None);
+
+        let mut iter_mut = list.iter_mut();
+        assert_eq!(iter_mut.advance_by(4), Ok(()));
+        assert_eq!(iter_mut.next(), Some(&mut 5));
+        assert_eq!(iter_mut.advance_by(1), Err(1));
+
+        let mut into_iter = list.into_iter();
+        assert_eq!(into_iter.advance_by(0), Ok(()));
+        assert_eq!(into_iter.advance_by(3), Ok(()));
+        assert_eq!(into_iter.next(), Some(4));
+        assert_eq!(into_iter.advance_by(2), Err(1));
+    }
+
+    #[test]
+    fn singleton() {
+        let mut list = List::singleton(7);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.peek(), Some(&7));
+        assert_eq!(list.pop(), Some(7));
+        assert_eq!(list.pop(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn reverse_between() {
+        let mut list = list_of(1..=5);
+        list.reverse_between(1, 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 4, 3, 2, 5]);
+
+        let mut list = list_of(1..=5);
+        list.reverse_between(0, 2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1, 4, 5]);
+
+        let mut list = list_of(1..=5);
+        list.reverse_between(0, 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+        assert_eq!(list.len(), 5);
+
+        let mut list = list_of(1..=5);
+        list.reverse_between(2, 2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reverse_between_out_of_range() {
+        let mut list = List::singleton(1);
+        list.reverse_between(0, 1);
+    }
+
+    #[test]
+    fn is_palindrome() {
+        let mut list = List::new();
+        assert!(list.is_palindrome());
+        list.push(1);
+        assert!(list.is_palindrome());
+        list.push(2);
+        assert!(!list.is_palindrome());
+        list.push(1);
+        assert!(list.is_palindrome());
+    }
+
+    #[test]
+    fn get_or_insert_front_with() {
+        let mut list = List::new();
+        *list.get_or_insert_front_with(|| 1) += 10;
+        assert_eq!(list.peek(), Some(&11));
+        assert_eq!(list.len(), 1);
+
+        list.push(2);
+        let front = list.get_or_insert_front_with(|| unreachable!());
+        assert_eq!(*front, 2);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn interleave() {
+        let mut a = List::new();
+        for i in [5, 3, 1] {
+            a.push(i);
+        }
+        let mut b = List::new();
+        for i in [4, 2] {
+            b.push(i);
+        }
+        let list = a.clone().interleave(b.clone());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.len(), 5);
+
+        let list = b.interleave(a);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 1, 4, 3, 5]);
+
+        let list = List::new().interleave(List::singleton(1));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn chunks_exact() {
+        let list = list_of(1..=7);
+        let (chunks, rest) = list.chunks_exact(3);
+        assert_eq!(chunks, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), vec![7]);
+        assert_eq!(rest.len(), 1);
+
+        let (chunks, rest) = List::singleton(1).chunks_exact(2);
+        assert!(chunks.is_empty());
+        assert_eq!(rest.len(), 1);
+    }
+
+    #[test]
+    fn chunk_sum() {
+        let list = list_of(1..=5);
+        assert_eq!(list.chunk_sum(2).iter().copied().collect::<Vec<_>>(), vec![3, 7, 5]);
+        assert_eq!(list.chunk_sum(5).iter().copied().collect::<Vec<_>>(), vec![15]);
+        assert!(List::<i32>::new().chunk_sum(2).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn chunk_sum_zero() {
+        List::singleton(1).chunk_sum(0);
+    }
+
+    #[test]
+    fn iter_indexed() {
+        let mut list = List::new();
+        for c in ['c', 'b', 'a'] {
+            list.push(c);
+        }
+        let pairs: Vec<(usize, &char)> = list.iter_indexed().collect();
+        assert_eq!(pairs, vec![(0, &'a'), (1, &'b'), (2, &'c')]);
+    }
+
+    #[test]
+    fn try_into_array() {
+        let mut list = List::new();
+        for s in ["c", "b", "a"] {
+            list.push(s.to_string());
+        }
+
+        let list = match list.try_into_array::<2>() {
+            Ok(_) => panic!("length is 3, not 2"),
+            Err(list) => list,
+        };
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.peek().map(|s| s.as_str()), Some("a"));
+
+        let array: [String; 3] = list.try_into_array().unwrap();
+        assert_eq!(array, ["a", "b", "c"]);
+
+        let empty: [i32; 0] = List::new().try_into_array().unwrap();
+        assert_eq!(empty, []);
+    }
+
+    #[test]
+    fn fold_links() {
+        let mut list = List::new();
+        for i in [3, 2, 1] {
+            list.push(i);
+        }
+        let joined = list.fold_links(String::new(), |mut acc, elem, is_last| {
+            acc.push_str(&elem.to_string());
+            if !is_last {
+                acc.push('-');
+            }
+            acc
+        });
+        assert_eq!(joined, "1-2-3");
+        assert_eq!(List::<i32>::new().fold_links(0, |acc, _, _| acc + 1), 0);
+    }
+
+    #[test]
+    fn split_even_odd() {
+        let list = list_of(0..=4);
+        let (evens, odds) = list.split_even_odd();
+        assert_eq!(evens.iter().copied().collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(odds.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!((evens.len(), odds.len()), (3, 2));
+
+        let (evens, odds) = List::<i32>::new().split_even_odd();
+        assert!(evens.is_empty() && odds.is_empty());
+    }
+
+    #[test]
+    fn reverse_in_groups() {
+        let mut list = list_of(1..=5);
+        list.reverse_in_groups(2, false);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 1, 4, 3, 5]);
+
+        let mut list = list_of(1..=5);
+        list.reverse_in_groups(2, true); //最后一组只有一个元素，反不反转都一样
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 1, 4, 3, 5]);
+
+        let mut list = list_of(1..=5);
+        list.reverse_in_groups(3, true);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1, 5, 4]);
+
+        let mut list = list_of(1..=5);
+        list.reverse_in_groups(3, false);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1, 4, 5]);
+        assert_eq!(list.len(), 5);
+
+        let mut list = list_of(1..=5);
+        list.reverse_in_groups(5, false);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn middle() {
+        let mut list = List::new();
+        assert_eq!(list.middle(), None);
+        list.push(1);
+        assert_eq!(list.middle(), Some(&1));
+        list.push(2);
+        assert_eq!(list.middle(), Some(&1)); //[2,1]，偶数取靠后的
+        list.push(3);
+        assert_eq!(list.middle(), Some(&2)); //[3,2,1]
+        list.push(4);
+        assert_eq!(list.middle(), Some(&2)); //[4,3,2,1]
+        list.push(5);
+        assert_eq!(list.middle(), Some(&3));
+    }
+
+    #[test]
+    fn has_cycle() {
+        let mut list = List::new();
+        assert!(!list.has_cycle());
+        for i in 0..10 {
+            list.push(i);
+            assert!(!list.has_cycle());
+        }
+    }
+
+    #[test]
+    fn nth_from_end() {
+        assert_eq!(List::<i32>::new().nth_from_end(0), None);
+        let list = list_of(1..=5);
+        assert_eq!(list.nth_from_end(0), Some(&5));
+        assert_eq!(list.nth_from_end(2), Some(&3));
+        assert_eq!(list.nth_from_end(4), Some(&1));
+        assert_eq!(list.nth_from_end(5), None);
+    }
+
+    #[test]
+    fn snapshot_restore() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let snap = list.snapshot();
+        list.pop();
+        list.push(10);
+        list.truncate(1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10]);
+        list.restore(snap);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn stress_long_list() {
+        const N: usize = 1_000_000;
+        let mut list = List::new();
+        for i in 0..N {
+            list.push(i);
+        }
+        assert_eq!(list.len(), N);
+        assert_eq!(list.iter().count(), N);
+        let copy = list.clone();
+        assert_eq!(copy.len(), N);
+        assert!(copy == list);
+        assert_eq!(format!("{:?}", copy).len(), format!("{:?}", list).len());
+        drop(copy);
+        drop(list);
+    }
+
+    #[test]
+    fn debug_prints_elems() {
+        let mut list = List::new();
+        assert_eq!(format!("{:?}", list), "[]");
+        list.push(2); list.push(1);
+        assert_eq!(format!("{:?}", list), "[1, 2]");
+    }
+
+    #[test]
+    fn intersperse() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let list = list.intersperse(0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 0, 2, 0, 3]);
+        assert_eq!(list.len(), 5);
+
+        let single = List::singleton(1).intersperse(0);
+        assert_eq!(single.iter().copied().collect::<Vec<_>>(), vec![1]);
+        assert!(List::<i32>::new().intersperse(0).is_empty());
+    }
+
+    #[test]
+    fn cursor() {
+        let list = list_of("12+3".chars());
+        let mut cursor = list.cursor();
+        let mut number = String::new();
+        while let Some(c) = cursor.peek().filter(|c| c.is_ascii_digit()) {
+            number.push(*c);
+            cursor.advance();
+        }
+        assert_eq!(number, "12");
+        assert_eq!(cursor.position(), 2);
+        assert_eq!(cursor.peek(), Some(&'+'));
+        cursor.advance();
+        cursor.advance();
+        assert_eq!(cursor.peek(), None);
+        cursor.advance();
+        assert_eq!(cursor.position(), 4);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    fn try_push() {
+        let mut list = List::new();
+        assert_eq!(list.try_push(2), Ok(()));
+        assert_eq!(list.try_push(1), Ok(()));
+        list.push(0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop(), Some(0));
+        assert_eq!(list.pop(), Some(1));
+    }
+
+    #[test]
+    fn collect_into() {
+        let mut list = List::singleton(0);
+        list.collect_into(1..3).collect_into(vec![3, 4]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(List::<i32>::new().collect_into(None).len(), 0);
+    }
+
+    #[test]
+    fn into_iter_marked() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let marked: Vec<_> = list.into_iter_marked().collect();
+        assert_eq!(marked, vec![(1, false), (2, false), (3, true)]);
+
+        let joined: String = List::from_ordered_iter(vec!["a", "b"]).into_iter_marked()
+            .map(|(s, last)| if last { s.to_string() } else { format!("{}, ", s) })
+            .collect();
+        assert_eq!(joined, "a, b");
+        assert_eq!(List::<i32>::new().into_iter_marked().count(), 0);
+    }
+
+    #[test]
+    fn try_get() {
+        let mut list = List::new();
+        list.push(2); list.push(1);
+        assert_eq!(list.try_get(0), Ok(&1));
+        assert_eq!(list.try_get(1), Ok(&2));
+        let err = list.try_get(5).unwrap_err();
+        assert_eq!(err, IndexError { index: 5, len: 2 });
+        assert_eq!(err.to_string(), "index out of bounds: the len is 2 but the index is 5");
+        let _: &dyn Error = &err;
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn dedup_all_counted() {
+        let mut list = list_of(['a', 'b', 'a', 'a', 'c']);
+        let counts = list.dedup_all_counted();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec!['a', 'b', 'c']);
+        assert_eq!(list.len(), 3);
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&'a'], 3);
+        assert_eq!(counts[&'b'], 1);
+        assert_eq!(counts[&'c'], 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn send_all() {
+        use std::sync::mpsc;
+        use std::thread;
+
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let (tx, rx) = mpsc::channel();
+        let handle = thread::spawn(move || rx.iter().collect::<Vec<i32>>());
+        assert!(list.send_all(&tx).is_ok());
+        drop(tx);
+        assert_eq!(handle.join().unwrap(), vec![1, 2, 3]);
+
+        let (tx, rx) = mpsc::channel();
+        drop(rx);
+        let err = List::singleton(7).send_all(&tx).unwrap_err();
+        assert_eq!(err.0, 7);
+    }
+
+    #[test]
+    fn prepend() {
+        let mut a = List::new();
+        a.push(4); a.push(3);
+        let mut b = List::new();
+        b.push(2); b.push(1);
+        a.prepend(&mut b);
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(a.len(), 4);
+        assert!(b.is_empty());
+        assert_eq!(b.iter().count(), 0);
+
+        //空链表放到前面，或者往空链表前面放
+        let mut empty = List::new();
+        a.prepend(&mut empty);
+        assert_eq!(a.len(), 4);
+        empty.prepend(&mut a);
+        assert_eq!(empty.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(empty.len(), 4);
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn reduce() {
+        let list = list_of(1..=4);
+        assert_eq!(list.reduce(|a, b| a + b), Some(10));
+        assert_eq!(List::<i32>::new().reduce(|a, b| a + b), None);
+    }
+
+    #[test]
+    fn scan_iter() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let sums: Vec<i32> = list.scan_iter(0, |sum, x| { *sum += x; Some(*sum) }).collect();
+        assert_eq!(sums, vec![1, 3, 6]);
+        let until: Vec<i32> = list.scan_iter(0, |sum, x| { *sum += x; (*sum < 3).then_some(*sum) }).collect();
+        assert_eq!(until, vec![1]);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn position_max_min() {
+        let list = list_of([3, 1, 5, 1, 5]);
+        assert_eq!(list.position_max(), Some(2));
+        assert_eq!(list.position_min(), Some(1));
+        let single = List::singleton(7);
+        assert_eq!(single.position_max(), Some(0));
+        assert_eq!(single.position_min(), Some(0));
+        assert_eq!(List::<i32>::new().position_max(), None);
+    }
+
+    #[test]
+    fn stack_list() {
+        let mut list: StackList<i32, 3> = StackList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.iter().next(), None);
+        for i in 1..=3 {
+            assert_eq!(list.push(i), Ok(()));
+        }
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.push(4), Err(4)); //满了，元素原样还回来
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.push(5), Ok(())); //pop出来的空位可以再用
+        assert_eq!(list.peek(), Some(&5));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 2, 1]);
+        assert_eq!(list.capacity(), 3);
+    }
+
+    #[test]
+    fn stack_list_drops_elems() {
+        use std::rc::Rc;
+        let elem = Rc::new(0);
+        {
+            let mut list: StackList<Rc<i32>, 4> = StackList::default();
+            list.push(elem.clone()).unwrap();
+            list.push(elem.clone()).unwrap();
+            assert_eq!(Rc::strong_count(&elem), 3);
+        }
+        assert_eq!(Rc::strong_count(&elem), 1);
+    }
+
+    #[test]
+    fn keep_indices() {
+        let mut list = list_of(['a', 'b', 'c', 'd']);
+        list.keep_indices(&[0, 2]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec!['a', 'c']);
+        assert_eq!(list.len(), 2);
+
+        list.keep_indices(&[1, 1, 9]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec!['c']);
+        list.keep_indices(&[]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn into_sorted_vec() {
+        let mut list = List::new();
+        for i in [3, 1, 4, 1, 5, 9, 2, 6] {
+            list.push(i);
+        }
+        let sorted = list.into_sorted_vec();
+        assert_eq!(sorted, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+        assert!(List::<i32>::new().into_sorted_vec().is_empty());
+    }
+
+    #[test]
+    fn partition_point() {
+        let list = list_of(1..=5);
+        assert_eq!(list.partition_point(|&x| x < 3), 2);
+        assert_eq!(list.partition_point(|_| true), 5);
+        assert_eq!(list.partition_point(|_| false), 0);
+        assert_eq!(List::<i32>::new().partition_point(|_| true), 0);
+    }
+
+    #[test]
+    fn retain_indexed() {
+        let mut list = list_of(['a', 'b', 'c', 'd']);
+        list.retain_indexed(|i, _| i % 2 == 0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec!['a', 'c']);
+        assert_eq!(list.len(), 2);
+        list.retain_indexed(|i, &c| i == 0 && c == 'a');
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec!['a']);
+    }
+
+    //教学用的粗略对比：同样在头部插入N个元素，看链表和VecDeque谁快。
+    //默认不跑，用 cargo test --release -- --ignored --nocapture bench_compare 查看耗时
+    #[test]
+    #[ignore]
+    fn bench_compare_vs_vecdeque() {
+        use std::collections::VecDeque;
+        use std::time::Instant;
+
+        const N: usize = 1_000_000;
+        let start = Instant::now();
+        let mut list = List::new();
+        for i in 0..N {
+            list.push(i);
+        }
+        let list_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut deque = VecDeque::new();
+        for i in 0..N {
+            deque.push_front(i);
+        }
+        let deque_time = start.elapsed();
+
+        println!("List::push {:?}, VecDeque::push_front {:?}", list_time, deque_time);
+        //不只比时间，两边的结果也必须完全一样
+        assert_eq!(list.len(), deque.len());
+        assert!(list.iter().eq(deque.iter()));
+    }
+
+    #[test]
+    fn retain_neighbors() {
+        let mut list = list_of([1, 5, 2, 2, 9, 3]);
+        //去掉比两边都大的尖峰，两头的元素只有一个邻居，不算尖峰
+        list.retain_neighbors(|prev, &cur, next| match (prev, next) {
+            (Some(&p), Some(&n)) => !(cur > p && cur > n),
+            _ => true,
+        });
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 2, 3]);
+        assert_eq!(list.len(), 4);
+
+        list.retain_neighbors(|_, _, _| false);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn peek_two() {
+        let mut list = List::new();
+        assert_eq!(list.peek_two(), (None, None));
+        list.push(3);
+        assert_eq!(list.peek_two(), (Some(&3), None));
+        list.push(2);
+        assert_eq!(list.peek_two(), (Some(&2), Some(&3)));
+        list.push(1);
+        assert_eq!(list.peek_two(), (Some(&1), Some(&2)));
+    }
+
+    #[test]
+    fn flat_map() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let flat = list.flat_map(|n| 0..n);
+        assert_eq!(flat.iter().copied().collect::<Vec<_>>(), vec![0, 0, 1, 0, 1, 2]);
+        assert_eq!(flat.len(), 6);
+        assert!(List::singleton(0).flat_map(|n| 0..n).is_empty());
+    }
+
+    #[test]
+    fn entry() {
+        let mut list = List::new();
+        list.push(("b", 2));
+        list.push(("a", 1));
+
+        //已经有的键，or_insert不会覆盖原来的值
+        assert!(matches!(list.entry("b"), Entry::Occupied(_)));
+        *list.entry("b").or_insert(100) += 10;
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![("a", 1), ("b", 12)]);
+
+        //没有的键，放到链表头
+        assert!(matches!(list.entry("c"), Entry::Vacant(..)));
+        assert_eq!(*list.entry("c").or_insert(3), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![("c", 3), ("a", 1), ("b", 12)]);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn fold_right() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let nested = list.fold_right(String::new(), |x, inner| {
+            if inner.is_empty() { x.to_string() } else { format!("{}({})", x, inner) }
+        });
+        assert_eq!(nested, "1(2(3))");
+        assert_eq!(list.fold_right(vec![], |&x, mut acc| { acc.push(x); acc }), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn stack_list_debug() {
+        let mut list: StackList<i32, 8> = StackList::new();
+        assert_eq!(format!("{:?}", list), "StackList { len: 0, capacity: 8, elems: [] }");
+        for i in (1..=3).rev() {
+            list.push(i).unwrap();
+        }
+        let debug = format!("{:?}", list);
+        assert!(debug.contains("len: 3"));
+        assert!(debug.contains("capacity: 8"));
+        assert_eq!(debug, "StackList { len: 3, capacity: 8, elems: [1, 2, 3] }");
+    }
+
+    #[test]
+    fn dedup_by() {
+        let mut list = list_of(["apple", "avocado", "banana", "blueberry", "apricot"].map(String::from));
+        list.dedup_by(|a, b| a.chars().next() == b.chars().next());
+        assert_eq!(list.iter().cloned().collect::<Vec<_>>(), vec!["apple", "banana", "apricot"]);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let mut list = list_of([10, 11, 20, 25, 31, 12]);
+        list.dedup_by_key(|x| *x / 10);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 20, 31, 12]);
+        assert_eq!(list.len(), 4);
+        let mut empty = List::<i32>::new();
+        empty.dedup_by_key(|x| *x);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn rotate_left() {
+        let mut list = list_of(1..=4);
+        list.rotate_left(1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 1]);
+        list.rotate_left(7); //7 % 4 == 3
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        list.rotate_left(4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        List::<i32>::new().rotate_left(3);
+    }
+
+    #[test]
+    fn rotate_to() {
+        let mut list = list_of(1..=4);
+        assert!(list.rotate_to(&3));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 4, 1, 2]);
+        assert!(!list.rotate_to(&9));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 4, 1, 2]);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    fn merge_k() {
+        let lists = vec![list_of([1, 4]), list_of([2]), List::new(), list_of([3, 5])];
+        let merged = List::merge_k(lists);
+        assert_eq!(merged.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(merged.len(), 5);
+        assert!(List::<i32>::merge_k(Vec::new()).is_empty());
+        assert!(List::<i32>::merge_k(vec![List::new(), List::new()]).is_empty());
+    }
+
+    #[test]
+    fn iter_step() {
+        let list = list_of(0..5);
+        assert_eq!(list.iter_step(2).copied().collect::<Vec<_>>(), vec![0, 2, 4]);
+        assert_eq!(list.iter_step(1).copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.iter_step(9).copied().collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be non-zero")]
+    fn iter_step_zero() {
+        let _ = List::singleton(1).iter_step(0);
+    }
+
+    #[test]
+    fn last() {
+        let mut list = List::new();
+        assert_eq!(list.last(), None);
+        list.push(1);
+        assert_eq!(list.last(), Some(&1));
+        list.push(2);
+        list.collect_into(vec![3, 4]);
+        assert_eq!(list.last(), Some(&4));
+        list.truncate(2);
+        assert_eq!(list.last(), Some(&1));
+        list.rotate_left(1);
+        assert_eq!(list.last(), Some(&2));
+        list.pop();
+        list.pop();
+        assert_eq!(list.last(), None);
+        for i in 0..10 {
+            list.push(i);
+            assert_eq!(list.last(), list.iter().last());
+        }
+    }
+
+    #[test]
+    fn reversed() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        let rev = list.reversed();
+        assert_eq!(rev.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(rev.len(), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(List::<i32>::new().reversed().is_empty());
+    }
+
+    #[test]
+    fn for_each_mut() {
+        let mut list = List::new();
+        list.push(3); list.push(2); list.push(1);
+        list.for_each_mut(|x| *x *= 2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 4, 6]);
+        let mut order = Vec::new();
+        list.for_each_mut(|x| order.push(*x));
+        assert_eq!(order, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn take_skip() {
+        let taken = list_of(1..=5).take(2);
+        assert_eq!(taken.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(taken.len(), 2);
+        let skipped = list_of(1..=5).skip(2);
+        assert_eq!(skipped.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(skipped.len(), 3);
+
+        assert_eq!(list_of(1..=5).take(9).len(), 5);
+        assert!(list_of(1..=5).skip(9).is_empty());
+    }
+
+    #[test]
+    fn extend() {
+        let mut list = List::singleton(0);
+        let slice: &[i32] = &[1, 2, 3];
+        list.extend(slice.iter());
+        list.extend(vec![4, 5]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(list.len(), 6);
+        assert_eq!(slice, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn coalesce() {
+        let list = list_of([1, 1, 5, 3, 3, 1]);
+        //相邻的相等值加在一起
+        let merged = list.coalesce(|a, b| if a == b { Ok(a + b) } else { Err((a, b)) });
+        assert_eq!(merged.iter().copied().collect::<Vec<_>>(), vec![2, 5, 6, 1]);
+        assert_eq!(merged.len(), 4);
+        assert!(List::<i32>::new().coalesce(|a, b| Ok(a + b)).is_empty());
+    }
+
+    #[test]
+    fn adjacent_map() {
+        let list = list_of([1, 3, 6, 10]);
+        let diffs = list.adjacent_map(|a, b| b - a);
+        assert_eq!(diffs.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(diffs.len(), 3);
+        assert!(List::singleton(1).adjacent_map(|a, b| a + b).is_empty());
+        assert!(List::<i32>::new().adjacent_map(|a, b| a + b).is_empty());
+    }
 }
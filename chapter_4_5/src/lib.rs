@@ -38,6 +38,22 @@ impl<T> List<T> {
     pub fn head(&self) -> Option<&T> {
         self.head.as_ref().map(|node| { &node.elem })
     }
+
+    ///两个链表只要共享了某个节点，从那个节点往后就全是共享的，所以只需比较最后一个节点是不是同一个
+    pub fn shares_tail_with(&self, other: &List<T>) -> bool {
+        match (self.last_node(), other.last_node()) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b), //比较的是指针，不是值
+            _ => false,
+        }
+    }
+
+    fn last_node(&self) -> Option<&Rc<Node<T>>> {
+        let mut node = self.head.as_ref()?;
+        while let Some(next) = node.next.as_ref() {
+            node = next;
+        }
+        Some(node)
+    }
 }
 
 //IntoIter   xx:不能实现
@@ -108,4 +124,20 @@ mod tests {
         assert_eq!(iter.next(), Some(&2));
         assert_eq!(iter.next(), Some(&1));
     }
+
+    #[test]
+    fn shares_tail_with() {
+        let mut empty = List::new();
+        let mut common = empty.append(1).append(2);
+        let a = common.append(3);
+        let b = common.append(4).append(5);
+        assert!(a.shares_tail_with(&b));
+        assert!(b.shares_tail_with(&common));
+        assert!(a.tail().shares_tail_with(&b.tail().tail()));
+
+        //值相同但各自构建的链表没有共享节点
+        let c = List::new().append(1).append(2).append(3);
+        assert!(!a.shares_tail_with(&c));
+        assert!(!a.shares_tail_with(&empty));
+    }
 }
@@ -1,5 +1,10 @@
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::cell::{Ref, RefMut, RefCell};
+use std::marker::PhantomData;
+use std::ptr;
+use std::collections::HashSet;
+
+use chapter_2_3::IndexError;
 
 ///# 使用 RefCell<T> 在运行时记录借用信息
 ///
@@ -24,6 +29,7 @@ use std::cell::{Ref, RefMut, RefCell};
 pub struct List<T> {
     head: Link<T>,
     tail: Link<T>,
+    len: usize,
 }
 
 type Link<T> = Option<Rc<RefCell<Node<T>>>>;
@@ -34,6 +40,18 @@ struct Node<T> {
     prev: Link<T>,
 }
 
+///weak_front/weak_back返回的观察句柄，里面是节点的弱引用，只能查询节点是否还活着
+///
+///不直接交出Weak，是因为外面upgrade出强引用后，pop_front等方法里的Rc::try_unwrap就会失败而panic
+pub struct NodeWatch<T>(Weak<RefCell<Node<T>>>);
+
+impl<T> NodeWatch<T> {
+    ///节点还没被释放时返回true
+    pub fn is_alive(&self) -> bool {
+        self.0.strong_count() > 0
+    }
+}
+
 impl<T> Node<T> {
     fn new(elem: T) -> Rc<RefCell<Self>> {
         Rc::new(RefCell::new(Node {
@@ -49,7 +67,57 @@ impl<T> List<T> {
         List {
             head: None,
             tail: None,
+            len: 0,
+        }
+    }
+
+    ///只有一个元素的链表，head和tail指向同一个节点
+    pub fn singleton(elem: T) -> Self {
+        let node = Node::new(elem);
+        List {
+            head: Some(node.clone()),
+            tail: Some(node),
+            len: 1,
+        }
+    }
+
+    //问题：为什么不在chapter_2_3的单链表上加一个to_doubly，直接转成这里的双向链表？
+    //答：双向链表写在chapter_4_5的main.rs里，是二进制crate，别的crate没法依赖它。
+    //   反过来这里可以依赖chapter_2_3，所以转换放在双向链表这边。
+
+    ///按顺序克隆单链表的元素建一个双向链表。单链表只能从头往后走，双向链表两头都能走
+    pub fn from_singly(list: &chapter_2_3::List<T>) -> List<T> where T: Clone {
+        List::build_from(list.iter().cloned())
+    }
+
+    ///一次遍历就把整条链表串好，省掉每次push_back时的 match self.tail.take()
+    pub fn build_from<I: IntoIterator<Item = T>>(iter: I) -> List<T> {
+        let mut list = List::new();
+        let mut iter = iter.into_iter();
+        if let Some(elem) = iter.next() {
+            let head = Node::new(elem);
+            let mut tail = head.clone();
+            let mut len = 1;
+            for elem in iter {
+                let node = Node::new(elem);
+                node.borrow_mut().prev = Some(tail.clone()); //新节点的上个节点指向当前尾节点
+                tail.borrow_mut().next = Some(node.clone()); //当前尾节点的下个节点指向新节点
+                tail = node;
+                len += 1;
+            }
+            list.head = Some(head);
+            list.tail = Some(tail);
+            list.len = len;
         }
+        list
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
     pub fn push_front(&mut self, elem: T) {
@@ -65,6 +133,7 @@ impl<T> List<T> {
                 self.head = Some(node);
             }
         }
+        self.len += 1;
     }
 
     pub fn push_back(&mut self, elem: T) {
@@ -80,6 +149,7 @@ impl<T> List<T> {
                 self.tail = Some(node);
             }
         }
+        self.len += 1;
     }
 
     pub fn pop_front(&mut self) -> Option<T> { //                           List -> Node <=> Next
@@ -93,6 +163,7 @@ impl<T> List<T> {
                     self.tail.take();
                 }
             }
+            self.len -= 1;
             Rc::try_unwrap(node) //rc变result。Returns the inner value, if the Rc has exactly one strong reference.
                 .ok()           //result变option。Converts from Result<T, E> to Option<T>.
                 .unwrap()       //拆包option，得到refcell。在确认Option不为None的情况下，可以用unwrap方法拆解出其中的值，并获取值的所有权。
@@ -112,10 +183,35 @@ impl<T> List<T> {
                     self.head.take();
                 }
             }
+            self.len -= 1;
             Rc::try_unwrap(node).ok().unwrap().into_inner().elem
         })
     }
 
+    ///从头到尾克隆出所有元素，不消耗链表
+    pub fn to_vec(&self) -> Vec<T> where T: Clone {
+        let mut vec = Vec::with_capacity(self.len);
+        let mut cur = self.head.clone(); //只是引用计数加1
+        while let Some(node) = cur {
+            let node = node.borrow();
+            vec.push(node.elem.clone());
+            cur = node.next.clone();
+        }
+        vec
+    }
+
+    ///顺着prev从尾到头克隆出所有元素
+    pub fn to_vec_rev(&self) -> Vec<T> where T: Clone {
+        let mut vec = Vec::with_capacity(self.len);
+        let mut cur = self.tail.clone();
+        while let Some(node) = cur {
+            let node = node.borrow();
+            vec.push(node.elem.clone());
+            cur = node.prev.clone();
+        }
+        vec
+    }
+
     pub fn peek_front(&self) -> Option<Ref<T>> {
         self.head.as_ref().map(|node| {
             Ref::map(node.borrow(), |node| &node.elem)
@@ -128,6 +224,19 @@ impl<T> List<T> {
         })
     }
 
+    ///与peek_front不同：如果节点正被可变借用，用try_borrow返回None而不是panic
+    pub fn try_peek_front(&self) -> Option<Ref<'_, T>> {
+        self.head.as_ref().and_then(|node| {
+            node.try_borrow().ok().map(|node| Ref::map(node, |node| &node.elem))
+        })
+    }
+
+    pub fn try_peek_back(&self) -> Option<Ref<'_, T>> {
+        self.tail.as_ref().and_then(|node| {
+            node.try_borrow().ok().map(|node| Ref::map(node, |node| &node.elem))
+        })
+    }
+
     pub fn peek_front_mut(&mut self) -> Option<RefMut<T>> {
         self.head.as_ref().map(|node| {
             RefMut::map(node.borrow_mut(), |node| &mut node.elem)
@@ -139,6 +248,480 @@ impl<T> List<T> {
             RefMut::map(node.borrow_mut(), |node| &mut node.elem)
         })
     }
+
+    ///同时可变借用头尾两个元素。少于两个节点时头尾是同一个RefCell，借两次会panic，所以返回None
+    pub fn front_back_mut(&mut self) -> Option<(RefMut<'_, T>, RefMut<'_, T>)> {
+        if self.len < 2 {
+            return None;
+        }
+        let front = RefMut::map(self.head.as_ref()?.borrow_mut(), |node| &mut node.elem);
+        let back = RefMut::map(self.tail.as_ref()?.borrow_mut(), |node| &mut node.elem);
+        Some((front, back))
+    }
+
+    ///旋转链表，让第一个满足pred的元素成为头节点，找不到时链表不变并返回false
+    pub fn rotate_to<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> bool {
+        let mut cur = self.head.clone();
+        let found = loop {
+            match cur {
+                Some(node) if pred(&node.borrow().elem) => break node,
+                Some(node) => cur = node.borrow().next.clone(),
+                None => return false,
+            }
+        };
+        let new_tail = match found.borrow_mut().prev.take() {
+            Some(prev) => prev,
+            None => return true, //已经是头节点了
+        };
+        new_tail.borrow_mut().next.take();
+        //把原来的尾和原来的头接起来，成环后再从found处断开
+        let old_head = self.head.take().unwrap();
+        let old_tail = self.tail.take().unwrap();
+        old_head.borrow_mut().prev = Some(old_tail.clone());
+        old_tail.borrow_mut().next = Some(old_head);
+        self.head = Some(found);
+        self.tail = Some(new_tail);
+        true
+    }
+
+    ///诊断用：统计有多少个节点被链表之外的地方持有(外部clone的Rc或者成环)
+    ///
+    ///链表内部每个节点正好有2个强引用：头节点是head和下个节点的prev，尾节点是tail和上个节点的next，
+    ///中间节点是上个节点的next和下个节点的prev，只有一个节点时是head和tail。超过2就说明有额外的引用
+    pub fn leaked_nodes(&self) -> usize {
+        let mut count = 0;
+        let mut cur = self.head.clone();
+        while let Some(node) = cur {
+            //减掉遍历时cur自己clone出来的那一个
+            if Rc::strong_count(&node) - 1 > 2 {
+                count += 1;
+            }
+            cur = node.borrow().next.clone();
+        }
+        count
+    }
+
+    ///头节点的观察句柄，不增加强引用计数，可以用来观察节点在移除后是否真的被释放了
+    pub fn weak_front(&self) -> Option<NodeWatch<T>> {
+        self.head.as_ref().map(|node| NodeWatch(Rc::downgrade(node)))
+    }
+
+    pub fn weak_back(&self) -> Option<NodeWatch<T>> {
+        self.tail.as_ref().map(|node| NodeWatch(Rc::downgrade(node)))
+    }
+
+    //下面几个私有方法只搬动节点，不拆出元素，用来给其他方法重新链接节点
+
+    ///摘下头节点，返回的节点prev和next都已经断开
+    fn pop_front_node(&mut self) -> Link<T> {
+        let node = self.head.take()?;
+        match node.borrow_mut().next.take() {
+            Some(next) => {
+                next.borrow_mut().prev.take();
+                self.head = Some(next);
+            }
+            None => {
+                self.tail.take();
+            }
+        }
+        self.len -= 1;
+        Some(node)
+    }
+
+    ///摘下尾节点，返回的节点prev和next都已经断开
+    fn pop_back_node(&mut self) -> Link<T> {
+        let node = self.tail.take()?;
+        match node.borrow_mut().prev.take() {
+            Some(prev) => {
+                prev.borrow_mut().next.take();
+                self.tail = Some(prev);
+            }
+            None => {
+                self.head.take();
+            }
+        }
+        self.len -= 1;
+        Some(node)
+    }
+
+    ///把一个已经断开的节点接到链表尾部
+    fn push_back_node(&mut self, node: Rc<RefCell<Node<T>>>) {
+        match self.tail.take() {
+            Some(tail) => {
+                tail.borrow_mut().next = Some(node.clone());
+                node.borrow_mut().prev = Some(tail);
+                self.tail = Some(node);
+            }
+            None => {
+                self.head = Some(node.clone());
+                self.tail = Some(node);
+            }
+        }
+        self.len += 1;
+    }
+
+    ///把一个已经断开的节点接到链表头部
+    fn push_front_node(&mut self, node: Rc<RefCell<Node<T>>>) {
+        match self.head.take() {
+            Some(head) => {
+                head.borrow_mut().prev = Some(node.clone());
+                node.borrow_mut().next = Some(head);
+                self.head = Some(node);
+            }
+            None => {
+                self.tail = Some(node.clone());
+                self.head = Some(node);
+            }
+        }
+        self.len += 1;
+    }
+
+    ///把链表中任意位置的一个节点摘下来，前后节点直接相连，摘下的节点prev和next都已经断开
+    fn unlink_node(&mut self, node: &Rc<RefCell<Node<T>>>) {
+        let prev = node.borrow_mut().prev.take();
+        let next = node.borrow_mut().next.take();
+        match &prev {
+            Some(prev) => prev.borrow_mut().next = next.clone(),
+            None => self.head = next.clone(),
+        }
+        match next {
+            Some(next) => next.borrow_mut().prev = prev,
+            None => self.tail = prev,
+        }
+        self.len -= 1;
+    }
+
+    ///把other的整条链接到self后面，O(1)，other变为空
+    fn splice_back(&mut self, other: &mut List<T>) {
+        let (head, tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            _ => return,
+        };
+        match self.tail.take() {
+            Some(old_tail) => {
+                old_tail.borrow_mut().next = Some(head.clone());
+                head.borrow_mut().prev = Some(old_tail);
+            }
+            None => self.head = Some(head),
+        }
+        self.tail = Some(tail);
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    ///把有序的other合并进同样有序的self，只重新链接节点。结束后other为空，相等的元素self的排在前面
+    pub fn merge(&mut self, other: &mut List<T>) where T: Ord {
+        let mut merged = List::new();
+        while let (Some(a), Some(b)) = (&self.head, &other.head) {
+            let from = if b.borrow().elem < a.borrow().elem { &mut *other } else { &mut *self };
+            merged.push_back_node(from.pop_front_node().unwrap());
+        }
+        //其中一个已经空了，另一个剩下的整段直接接上
+        merged.splice_back(self);
+        merged.splice_back(other);
+        *self = merged;
+    }
+
+    ///第index个节点(克隆出来的Rc)，越界返回None
+    fn node_at(&self, index: usize) -> Link<T> {
+        if index >= self.len {
+            return None;
+        }
+        let mut cur = self.head.clone();
+        for _ in 0..index {
+            cur = cur.and_then(|node| node.borrow().next.clone());
+        }
+        cur
+    }
+
+    ///教学用：第index个节点当前的强引用计数(不算这里查询时临时clone的那一个)，越界返回None
+    ///
+    ///链表内部正常情况下是2，在外面clone一个节点的Rc就会变成3。
+    ///注意移除节点前必须先drop掉外面的Rc，否则pop_front/pop_back里的Rc::try_unwrap会失败而panic
+    pub fn node_strong_count(&self, index: usize) -> Option<usize> {
+        self.node_at(index).map(|node| Rc::strong_count(&node) - 1)
+    }
+
+    ///LRU用：把第index个节点移到头部，找到节点后只需O(1)地改几个链接。越界返回false
+    pub fn move_to_front(&mut self, index: usize) -> bool {
+        match self.node_at(index) {
+            Some(node) => {
+                if index > 0 {
+                    self.unlink_node(&node);
+                    self.push_front_node(node);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    ///只保留前len个元素，多出来的从尾部一个个pop_back掉。len不小于当前长度时什么都不做
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop_back();
+        }
+    }
+
+    ///从头部开始，只要元素满足pred就摘下来，遇到第一个不满足的就停。摘下的节点按原顺序组成新链表返回
+    pub fn pop_front_while<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> List<T> {
+        let mut popped = List::new();
+        while self.head.as_ref().is_some_and(|node| pred(&node.borrow().elem)) {
+            popped.push_back_node(self.pop_front_node().unwrap());
+        }
+        popped
+    }
+
+    ///从尾部开始往前摘，返回的链表仍然保持原来的前后顺序
+    pub fn pop_back_while<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> List<T> {
+        let mut popped = List::new();
+        while self.tail.as_ref().is_some_and(|node| pred(&node.borrow().elem)) {
+            popped.push_front_node(self.pop_back_node().unwrap());
+        }
+        popped
+    }
+
+    //问题：为什么不给这个链表实现Index/IndexMut，让list[2]和list[2] = x可以用？
+    //答：Index::index必须返回&T，但元素被包在RefCell里，只能通过Ref<T>/RefMut<T>临时借出来，
+    //   一个普通的&T没法在运行时记录借用信息。所以这里提供返回Ref/RefMut的get/get_mut，
+    //   以及克隆取值的at和替换赋值的set来代替下标语法，at和set越界时和下标一样会panic。
+
+    ///第index个元素的借用，越界返回None
+    pub fn get(&self, index: usize) -> Option<Ref<'_, T>> {
+        if index >= self.len {
+            return None;
+        }
+        //节点归链表所有，在&self期间不会被释放，所以可以把裸指针转回引用
+        let node = unsafe { &*nth_node(Rc::as_ptr(self.head.as_ref().unwrap()), index) };
+        Some(Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<RefMut<'_, T>> {
+        if index >= self.len {
+            return None;
+        }
+        let node = unsafe { &*nth_node(Rc::as_ptr(self.head.as_ref().unwrap()), index) };
+        Some(RefMut::map(node.borrow_mut(), |node| &mut node.elem))
+    }
+
+    ///和get一样，但越界时返回带下标和长度的IndexError。元素在RefCell里，所以给的是Ref而不是&T
+    pub fn try_get(&self, i: usize) -> Result<Ref<'_, T>, IndexError> {
+        self.get(i).ok_or(IndexError { index: i, len: self.len })
+    }
+
+    ///没有初始值的fold：用pop_front一个个取出来，第一个元素当累加器，空链表返回None
+    pub fn reduce<F: FnMut(T, T) -> T>(mut self, mut f: F) -> Option<T> {
+        let mut acc = self.pop_front()?;
+        while let Some(elem) = self.pop_front() {
+            acc = f(acc, elem);
+        }
+        Some(acc)
+    }
+
+    ///相当于list[index]：克隆出第index个元素，越界时panic
+    pub fn at(&self, index: usize) -> T where T: Clone {
+        match self.get(index) {
+            Some(elem) => elem.clone(),
+            None => panic!("index out of bounds: the len is {} but the index is {}", self.len, index),
+        }
+    }
+
+    ///相当于list[index] = elem：替换第index个元素并返回旧值，越界时panic
+    pub fn set(&mut self, index: usize, elem: T) -> T {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(mut slot) => std::mem::replace(&mut *slot, elem),
+            None => panic!("index out of bounds: the len is {} but the index is {}", len, index),
+        }
+    }
+
+    ///链接被弄乱之后修复：顺着next从头走一遍，重新设置每个节点的prev，
+    ///遇到已经走过的节点说明next形成了环，就在那里断开。最后按走到的节点重新设置tail和len，返回是否发现了环
+    pub fn repair_links(&mut self) -> bool {
+        let mut visited = HashSet::new();
+        let mut prev: Link<T> = None;
+        let mut cur = self.head.clone();
+        let mut len = 0;
+        let mut cycle = false;
+        while let Some(node) = cur {
+            if !visited.insert(Rc::as_ptr(&node)) {
+                //走过的节点至少有一个，所以prev一定有值；剪掉指回去的next，环就断了
+                prev.as_ref().unwrap().borrow_mut().next = None;
+                cycle = true;
+                break;
+            }
+            node.borrow_mut().prev = prev.clone();
+            len += 1;
+            cur = node.borrow().next.clone();
+            prev = Some(node);
+        }
+        self.tail = prev;
+        self.len = len;
+        cycle
+    }
+
+    ///检查链表结构是否正确，有问题时panic并说明是哪里不对：从头顺着next走和从尾顺着prev走，
+    ///两边的节点个数都要等于len，经过的节点顺序要正好相反，head和tail要分别是两头的节点。
+    ///在自己的测试里改动了链接之后可以用它验证。只在测试或者打开debug-invariants feature时才有
+    #[cfg(any(test, feature = "debug-invariants"))]
+    pub fn assert_consistent(&self) {
+        if let Some(err) = self.consistency_error() {
+            panic!("inconsistent list: {}", err);
+        }
+    }
+
+    #[cfg(test)]
+    fn check_invariants(&self) -> bool {
+        self.consistency_error().is_none()
+    }
+
+    #[cfg(any(test, feature = "debug-invariants"))]
+    fn consistency_error(&self) -> Option<String> {
+        //最多走len+1步，next或prev成环时也能停下来
+        let walk = |start: &Link<T>, step: fn(&Node<T>) -> Link<T>| {
+            let mut nodes = Vec::new();
+            let mut cur = start.clone();
+            while let Some(node) = cur {
+                if nodes.len() > self.len {
+                    break;
+                }
+                cur = step(&node.borrow());
+                nodes.push(node);
+            }
+            nodes
+        };
+        let forward = walk(&self.head, |node| node.next.clone());
+        let backward = walk(&self.tail, |node| node.prev.clone());
+        if forward.len() != self.len {
+            return Some(format!("forward traversal reached {} nodes but len is {}", forward.len(), self.len));
+        }
+        if backward.len() != self.len {
+            return Some(format!("backward traversal reached {} nodes but len is {}", backward.len(), self.len));
+        }
+        if !forward.iter().zip(backward.iter().rev()).all(|(a, b)| Rc::ptr_eq(a, b)) {
+            return Some("forward and backward traversals visit nodes in different orders".to_string());
+        }
+        let same = |a: Option<&Rc<RefCell<Node<T>>>>, b: &Link<T>| a.map(Rc::as_ptr) == b.as_ref().map(Rc::as_ptr);
+        if !same(forward.last(), &self.tail) {
+            return Some("tail is not the last node reached from head".to_string());
+        }
+        if !same(backward.last(), &self.head) {
+            return Some("head is not the last node reached from tail".to_string());
+        }
+        None
+    }
+
+    ///只保留f返回true的元素，f还会拿到元素在删除之前的原始下标
+    pub fn retain_indexed<F: FnMut(usize, &T) -> bool>(&mut self, mut f: F) {
+        let mut cur = self.head.clone();
+        let mut index = 0;
+        while let Some(node) = cur {
+            cur = node.borrow().next.clone(); //先记住下一个，摘掉当前节点后它的next会被清空
+            if !f(index, &node.borrow().elem) {
+                self.unlink_node(&node);
+            }
+            index += 1;
+        }
+    }
+
+    ///从中间拆成两半，前一半有 ceil(len/2) 个。用快慢指针找中点：fast一次走两步，slow一次走一步，
+    ///fast走到头时slow就是前一半的最后一个节点，在它后面剪开
+    pub fn split_halves(mut self) -> (List<T>, List<T>) {
+        let mut slow = match self.head.clone() {
+            Some(head) => head,
+            None => return (self, List::new()),
+        };
+        let mut fast = slow.clone();
+        loop {
+            let next_next = fast.borrow().next.as_ref().and_then(|next| next.borrow().next.clone());
+            match next_next {
+                Some(node) => fast = node,
+                None => break,
+            }
+            let next = slow.borrow().next.clone().unwrap();
+            slow = next;
+        }
+        let mut back = List::new();
+        if let Some(second) = slow.borrow_mut().next.take() {
+            second.borrow_mut().prev.take();
+            back.head = Some(second);
+            back.tail = self.tail.take();
+            back.len = self.len / 2;
+        }
+        self.tail = Some(slow);
+        self.len -= back.len;
+        (self, back)
+    }
+
+    ///只交换头尾两个元素的值，不动节点。少于两个节点时头尾是同一个RefCell，不能借两次，直接返回
+    pub fn swap_ends(&mut self) {
+        if self.len < 2 {
+            return;
+        }
+        if let (Some(head), Some(tail)) = (&self.head, &self.tail) {
+            std::mem::swap(&mut head.borrow_mut().elem, &mut tail.borrow_mut().elem);
+        }
+    }
+
+    ///顺着prev从尾往头找，返回最后一个满足pred的元素的下标（从头开始数），找不到返回None
+    pub fn rfind<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<usize> {
+        let mut index = self.len;
+        let mut cur = self.tail.clone();
+        while let Some(node) = cur {
+            index -= 1;
+            if pred(&node.borrow().elem) {
+                return Some(index);
+            }
+            cur = node.borrow().prev.clone();
+        }
+        None
+    }
+
+    ///按值从尾到头遍历，每次pop_back一个，效果和into_iter().rev()一样
+    pub fn into_iter_rev(mut self) -> impl Iterator<Item = T> {
+        std::iter::from_fn(move || self.pop_back())
+    }
+
+    ///从头尾两端同时往中间比较，只比较len/2对，奇数长度时正中间的节点不会和自己比
+    pub fn is_palindrome(&self) -> bool where T: PartialEq {
+        let mut front = self.head.clone();
+        let mut back = self.tail.clone();
+        for _ in 0..self.len / 2 {
+            //走的步数不超过len/2，两边一定还有节点
+            let (Some(f), Some(b)) = (front, back) else { unreachable!() };
+            if f.borrow().elem != b.borrow().elem {
+                return false;
+            }
+            front = f.borrow().next.clone();
+            back = b.borrow().prev.clone();
+        }
+        true
+    }
+
+    ///从头部按顺序pop最多n个元素放进Vec，链表不够长时有多少取多少。Vec的容量按实际能取到的个数预先分配
+    pub fn pop_front_batch(&mut self, n: usize) -> Vec<T> {
+        let mut batch = Vec::with_capacity(n.min(self.len));
+        while batch.len() < n {
+            match self.pop_front() {
+                Some(elem) => batch.push(elem),
+                None => break,
+            }
+        }
+        batch
+    }
+}
+
+//实现Drop
+//相邻节点通过next和prev互相持有强引用，形成引用环，不手动打断的话节点永远不会被释放
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        self.tail.take();
+        let mut cur = self.head.take();
+        while let Some(node) = cur {
+            cur = node.borrow_mut().next.take();
+            node.borrow_mut().prev.take();
+        }
+    }
 }
 
 //实现迭代器
@@ -158,18 +741,167 @@ impl<T> Iterator for IntoIter<T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.0.pop_front()
     }
+
+    //跳过的元素直接pop掉，不用经过默认实现里一次次调用next
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.0.len() {
+            self.0.truncate(0); //不够长，全部丢掉
+            return None;
+        }
+        for _ in 0..n {
+            self.0.pop_front();
+        }
+        self.0.pop_front()
+    }
 }
 
 impl<T> DoubleEndedIterator for IntoIter<T> {
     fn next_back(&mut self) -> Option<T> {
         self.0.pop_back()
     }
+
+    fn nth_back(&mut self, n: usize) -> Option<T> {
+        if n >= self.0.len() {
+            self.0.truncate(0);
+            return None;
+        }
+        self.0.truncate(self.0.len() - n); //truncate从尾部pop掉n个
+        self.0.pop_back()
+    }
+}
+
+//仿照 split_at_mut：把链表分成互不重叠的前后两段，各自可变访问
+//视图里只存裸指针，PhantomData<&'a mut List<T>> 保证在视图存活期间链表被独占借用，节点不会被释放
+pub struct FrontMut<'a, T> {
+    head: *const RefCell<Node<T>>,
+    len: usize,
+    _list: PhantomData<&'a mut List<T>>,
+}
+
+pub struct BackMut<'a, T> {
+    head: *const RefCell<Node<T>>,
+    len: usize,
+    _list: PhantomData<&'a mut List<T>>,
+}
+
+///从node开始往后走n步。调用者要保证这n步内的节点都存在且还活着
+unsafe fn nth_node<T>(mut node: *const RefCell<Node<T>>, n: usize) -> *const RefCell<Node<T>> {
+    for _ in 0..n {
+        node = Rc::as_ptr((*node).borrow().next.as_ref().unwrap());
+    }
+    node
+}
+
+///取视图里第index个元素的可变借用，index必须小于视图长度
+unsafe fn view_elem_mut<'b, T>(head: *const RefCell<Node<T>>, index: usize) -> RefMut<'b, T> {
+    let node = &*nth_node(head, index);
+    RefMut::map(node.borrow_mut(), |node| &mut node.elem)
+}
+
+impl<T> List<T> {
+    ///前一段是[0, index)，后一段是[index, len)。index > len 时panic
+    pub fn split_mut_at(&mut self, index: usize) -> (FrontMut<'_, T>, BackMut<'_, T>) {
+        assert!(index <= self.len, "split index (is {}) should be <= len (is {})", index, self.len);
+        let head = self.head.as_ref().map_or(ptr::null(), Rc::as_ptr);
+        let mid = if index == self.len {
+            ptr::null()
+        } else {
+            unsafe { nth_node(head, index) }
+        };
+        (
+            FrontMut { head, len: index, _list: PhantomData },
+            BackMut { head: mid, len: self.len - index, _list: PhantomData },
+        )
+    }
+}
+
+impl<'a, T> FrontMut<'a, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<RefMut<'_, T>> {
+        if index >= self.len {
+            return None;
+        }
+        //index在[0, len)内，只会访问到前一段的节点
+        Some(unsafe { view_elem_mut(self.head, index) })
+    }
+}
+
+impl<'a, T> BackMut<'a, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<RefMut<'_, T>> {
+        if index >= self.len {
+            return None;
+        }
+        //index在[0, len)内，只会访问到后一段的节点
+        Some(unsafe { view_elem_mut(self.head, index) })
+    }
 }
 
 
+///可以边走边删的游标，cur为None表示已经走到了末尾
+pub struct CursorMut<'a, T> {
+    list: &'a mut List<T>,
+    cur: Link<T>,
+}
+
+impl<T> List<T> {
+    ///指向头节点的游标
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        let cur = self.head.clone();
+        CursorMut { list: self, cur }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    pub fn current(&self) -> Option<Ref<'_, T>> {
+        self.cur.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    ///往后走一个节点，已经在末尾时什么都不做
+    pub fn move_next(&mut self) {
+        if let Some(node) = self.cur.take() {
+            self.cur = node.borrow().next.clone();
+        }
+    }
+
+    ///删掉当前节点并返回它的值，游标移到下一个节点
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.cur.take()?;
+        self.cur = node.borrow().next.clone();
+        self.list.unlink_node(&node);
+        //摘下来以后只剩游标手里的这一个强引用
+        Some(Rc::try_unwrap(node).ok().unwrap().into_inner().elem)
+    }
+
+    ///只要当前元素满足pred就一直删下去，走到末尾或遇到不满足的元素时停下，返回删了几个
+    pub fn remove_while<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> usize {
+        let mut removed = 0;
+        while self.cur.as_ref().is_some_and(|node| pred(&node.borrow().elem)) {
+            self.remove_current();
+            removed += 1;
+        }
+        removed
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::List;
+    use super::{IndexError, List, NodeWatch};
+    use std::rc::Rc;
 
     #[test]
     fn map() {
@@ -256,6 +988,36 @@ mod tests {
         assert_eq!(*list.peek_back_mut().unwrap(), 1);
     }
 
+    #[test]
+    fn try_peek() {
+        let mut list = List::new();
+        assert!(list.try_peek_front().is_none());
+        list.push_back(1);
+        list.push_back(2);
+        assert_eq!(*list.try_peek_front().unwrap(), 1);
+        assert_eq!(*list.try_peek_back().unwrap(), 2);
+
+        let head = list.head.as_ref().unwrap().borrow_mut();
+        assert!(list.try_peek_front().is_none()); //头节点正被可变借用，不会panic
+        assert_eq!(*list.try_peek_back().unwrap(), 2);
+        drop(head);
+        assert_eq!(*list.try_peek_front().unwrap(), 1);
+    }
+
+    #[test]
+    fn to_vec() {
+        let mut list = List::new();
+        assert_eq!(list.to_vec(), Vec::<i32>::new());
+        assert_eq!(list.to_vec_rev(), Vec::<i32>::new());
+
+        list.push_back(2);
+        list.push_back(3);
+        list.push_front(1);
+        assert_eq!(list.to_vec(), vec![1, 2, 3]);
+        assert_eq!(list.to_vec_rev(), vec![3, 2, 1]);
+        assert_eq!(list.len(), 3); //链表没有被消耗
+    }
+
     #[test]
     fn into_iter() {
         let mut list = List::new();
@@ -270,6 +1032,511 @@ mod tests {
         assert_eq!(iter.next_back(), None);
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn build_from() {
+        let mut list = List::build_from(0..1000);
+        assert_eq!(list.len(), 1000);
+        assert_eq!(*list.peek_front().unwrap(), 0);
+        assert_eq!(*list.peek_back().unwrap(), 999);
+
+        assert_eq!(list.pop_back(), Some(999));
+        for i in 0..999 {
+            assert_eq!(list.pop_front(), Some(i));
+        }
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+
+        let list: List<i32> = List::build_from(Vec::new());
+        assert!(list.is_empty());
+        assert!(list.peek_front().is_none());
+    }
+
+    #[test]
+    fn split_mut_at() {
+        let mut list = List::build_from(1..=6);
+        {
+            let (mut front, mut back) = list.split_mut_at(2);
+            assert_eq!(front.len(), 2);
+            assert_eq!(back.len(), 4);
+            assert!(front.get_mut(2).is_none());
+            assert!(back.get_mut(4).is_none());
+
+            //两段交替修改，同时持有两边的RefMut
+            for i in 0..4 {
+                let mut b = back.get_mut(i).unwrap();
+                if let Some(mut f) = front.get_mut(i) {
+                    *f *= 10;
+                }
+                *b += 100;
+            }
+        }
+        assert_eq!(list.len(), 6);
+        let elems: Vec<i32> = list.into_iter().collect();
+        assert_eq!(elems, vec![10, 20, 103, 104, 105, 106]);
+
+        let mut list = List::build_from(1..=3);
+        let (front, mut back) = list.split_mut_at(0);
+        assert!(front.is_empty());
+        assert_eq!(*back.get_mut(0).unwrap(), 1);
+        let (front, back) = list.split_mut_at(3);
+        assert_eq!(front.len(), 3);
+        assert!(back.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_mut_at_out_of_bounds() {
+        let mut list = List::build_from(1..=3);
+        list.split_mut_at(4);
+    }
+
+    #[test]
+    fn rotate_to() {
+        let mut list = List::build_from(1..=5);
+        assert!(list.rotate_to(|&x| x == 3));
+        assert_eq!(list.to_vec(), vec![3, 4, 5, 1, 2]);
+        assert_eq!(list.to_vec_rev(), vec![2, 1, 5, 4, 3]);
+        assert_eq!(list.len(), 5);
+
+        assert!(list.rotate_to(|&x| x == 3)); //已经在头部
+        assert_eq!(list.to_vec(), vec![3, 4, 5, 1, 2]);
+
+        assert!(list.rotate_to(|&x| x == 2)); //尾节点
+        assert_eq!(list.to_vec(), vec![2, 3, 4, 5, 1]);
+        assert_eq!(list.to_vec_rev(), vec![1, 5, 4, 3, 2]);
+
+        assert!(!list.rotate_to(|&x| x > 10));
+        assert_eq!(list.to_vec(), vec![2, 3, 4, 5, 1]);
+
+        let mut empty: List<i32> = List::new();
+        assert!(!empty.rotate_to(|_| true));
+    }
+
+    #[test]
+    fn singleton() {
+        let mut list = List::singleton(7);
+        assert_eq!(list.len(), 1);
+        assert!(Rc::ptr_eq(list.head.as_ref().unwrap(), list.tail.as_ref().unwrap()));
+        assert_eq!(list.pop_front(), Some(7));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty());
+
+        let mut list = List::singleton(8);
+        assert_eq!(list.pop_back(), Some(8));
+        assert_eq!(list.pop_front(), None);
+    }
+
+    #[test]
+    fn leaked_nodes() {
+        let list = List::build_from(1..=4);
+        assert_eq!(list.leaked_nodes(), 0);
+
+        //在链表外面持有头节点和第三个节点
+        let head = list.head.clone().unwrap();
+        let third = head.borrow().next.as_ref().unwrap().borrow().next.clone().unwrap();
+        assert_eq!(list.leaked_nodes(), 2);
+
+        drop(head);
+        assert_eq!(list.leaked_nodes(), 1);
+        drop(third);
+        assert_eq!(list.leaked_nodes(), 0);
+
+        let list = List::singleton(1);
+        assert_eq!(list.leaked_nodes(), 0);
+    }
+
+    #[test]
+    fn weak_handles() {
+        let mut list = List::build_from(1..=3);
+        let front = list.weak_front().unwrap();
+        let back = list.weak_back().unwrap();
+        let middle = NodeWatch(Rc::downgrade(&list.node_at(1).unwrap()));
+
+        assert_eq!(list.pop_front(), Some(1));
+        assert!(!front.is_alive()); //移除后立刻被释放
+        assert!(middle.is_alive());
+        assert!(back.is_alive());
+
+        drop(list);
+        assert!(!middle.is_alive()); //Drop打断了引用环
+        assert!(!back.is_alive());
+
+        let list: List<i32> = List::new();
+        assert!(list.weak_front().is_none());
+        assert!(list.weak_back().is_none());
+    }
+
+    #[test]
+    fn merge() {
+        let mut a = List::build_from([1, 3, 5, 7]);
+        let mut b = List::build_from([2, 3, 6]);
+        a.merge(&mut b);
+        assert_eq!(a.len(), 7);
+        assert!(b.is_empty());
+        assert!(b.peek_front().is_none() && b.peek_back().is_none());
+        assert_eq!(a.to_vec_rev(), vec![7, 6, 5, 3, 3, 2, 1]);
+
+        let mut popped = Vec::new();
+        while let Some(elem) = a.pop_front() {
+            popped.push(elem);
+        }
+        assert_eq!(popped, vec![1, 2, 3, 3, 5, 6, 7]);
+
+        let mut a = List::new();
+        let mut b = List::build_from([1, 2]);
+        a.merge(&mut b);
+        assert_eq!(a.to_vec(), vec![1, 2]);
+        assert_eq!(*a.peek_back().unwrap(), 2);
+        a.merge(&mut List::new());
+        assert_eq!(a.to_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn node_strong_count() {
+        let mut list = List::new();
+        list.push_back(1);
+        assert_eq!(list.node_strong_count(0), Some(2)); //head和tail
+        list.push_back(2);
+        list.push_back(3);
+        assert_eq!(list.node_strong_count(1), Some(2)); //上个节点的next和下个节点的prev
+        assert_eq!(list.node_strong_count(3), None);
+
+        let handle = list.node_at(1).unwrap(); //在链表外面持有第二个节点
+        assert_eq!(list.node_strong_count(1), Some(3));
+        assert_eq!(Rc::strong_count(&handle), 3);
+
+        //移除尾节点后，第二个节点变成尾节点：上个节点的next和tail，再加外面的handle
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.node_strong_count(1), Some(3));
+
+        //要移除第二个节点本身，先drop掉外面的handle
+        drop(handle);
+        assert_eq!(list.node_strong_count(1), Some(2));
+        let watch = list.weak_back().unwrap();
+        assert_eq!(list.pop_back(), Some(2));
+        assert!(!watch.is_alive()); //移除后强引用计数降到0，节点被释放
+        assert_eq!(list.node_strong_count(0), Some(2)); //只剩一个节点：head和tail
+    }
+
+    #[test]
+    fn move_to_front() {
+        let mut list = List::build_from(1..=5);
+        assert!(list.move_to_front(2)); //中间节点
+        assert_eq!(list.to_vec(), vec![3, 1, 2, 4, 5]);
+        assert_eq!(list.to_vec_rev(), vec![5, 4, 2, 1, 3]);
+
+        assert!(list.move_to_front(4)); //尾节点
+        assert_eq!(list.to_vec(), vec![5, 3, 1, 2, 4]);
+        assert_eq!(list.to_vec_rev(), vec![4, 2, 1, 3, 5]);
+        assert_eq!(*list.peek_back().unwrap(), 4);
+
+        assert!(list.move_to_front(0));
+        assert!(!list.move_to_front(5));
+        assert_eq!(list.to_vec(), vec![5, 3, 1, 2, 4]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.leaked_nodes(), 0);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut list = List::build_from(1..=5);
+        list.truncate(2);
+        assert_eq!(list.len(), 2);
+        assert_eq!(*list.peek_back().unwrap(), 2);
+        assert!(list.tail.as_ref().unwrap().borrow().next.is_none());
+        assert_eq!(list.to_vec_rev(), vec![2, 1]);
+
+        list.truncate(5);
+        assert_eq!(list.to_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn pop_while() {
+        let mut list = List::build_from([1, 2, 5, 3, 8, 9]);
+        let front = list.pop_front_while(|&x| x < 3);
+        assert_eq!(front.to_vec(), vec![1, 2]);
+        assert_eq!(front.to_vec_rev(), vec![2, 1]);
+        assert_eq!(list.to_vec(), vec![5, 3, 8, 9]);
+
+        let back = list.pop_back_while(|&x| x > 4);
+        assert_eq!(back.to_vec(), vec![8, 9]);
+        assert_eq!(back.len(), 2);
+        assert_eq!(list.to_vec(), vec![5, 3]);
+        assert_eq!(list.to_vec_rev(), vec![3, 5]);
+
+        assert!(list.pop_front_while(|&x| x > 100).is_empty()); //第一个就不满足
+        let all = list.pop_back_while(|_| true);
+        assert_eq!(all.to_vec(), vec![5, 3]);
+        assert!(list.is_empty());
+        assert!(list.peek_front().is_none());
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut list = List::build_from(1..=4);
+        assert_eq!(*list.get(0).unwrap(), 1);
+        assert_eq!(*list.get(3).unwrap(), 4);
+        assert!(list.get(4).is_none());
+
+        *list.get_mut(1).unwrap() *= 10;
+        assert_eq!(list.at(1), 20);
+        assert_eq!(list.set(2, 30), 3);
+        assert_eq!(list.to_vec(), vec![1, 20, 30, 4]);
+        assert!(list.get_mut(4).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn at_out_of_bounds() {
+        List::build_from(1..=2).at(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn set_out_of_bounds() {
+        List::build_from(1..=2).set(2, 0);
+    }
+
+    #[test]
+    fn try_get() {
+        let list = List::build_from(vec![1, 2]);
+        assert_eq!(*list.try_get(0).unwrap(), 1);
+        assert_eq!(*list.try_get(1).unwrap(), 2);
+        let err = list.try_get(2).unwrap_err();
+        assert_eq!(err, IndexError { index: 2, len: 2 });
+        assert_eq!(err.to_string(), "index out of bounds: the len is 2 but the index is 2");
+    }
+
+    #[test]
+    fn reduce() {
+        let list = List::build_from(vec![1, 2, 3, 4]);
+        assert_eq!(list.reduce(|a, b| a + b), Some(10));
+        let list = List::build_from(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(list.reduce(|a, b| a + &b), Some("ab".to_string()));
+        assert_eq!(List::<i32>::new().reduce(|a, b| a + b), None);
+    }
+
+    #[test]
+    fn repair_bad_prev() {
+        let mut list = List::build_from(vec![1, 2, 3, 4]);
+        assert!(list.check_invariants());
+        let first = list.node_at(0);
+        list.node_at(2).unwrap().borrow_mut().prev = first; //3的prev错指向了1
+        assert!(!list.check_invariants());
+        assert!(!list.repair_links());
+        assert!(list.check_invariants());
+        assert_eq!(list.to_vec_rev(), vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn repair_breaks_cycle() {
+        let mut list = List::build_from(vec![1, 2, 3, 4]);
+        let second = list.node_at(1);
+        list.node_at(3).unwrap().borrow_mut().next = second; //4的next指回2，形成环
+        assert!(!list.check_invariants());
+        assert!(list.repair_links());
+        assert!(list.check_invariants());
+        assert_eq!(list.to_vec(), vec![1, 2, 3, 4]);
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.pop_back(), Some(4));
+    }
+
+    #[test]
+    fn front_back_mut() {
+        assert!(List::<i32>::new().front_back_mut().is_none());
+        assert!(List::singleton(1).front_back_mut().is_none());
+
+        let mut list = List::build_from(vec![1, 2]);
+        {
+            let (mut front, mut back) = list.front_back_mut().unwrap();
+            std::mem::swap(&mut *front, &mut *back);
+        }
+        assert_eq!(list.to_vec(), vec![2, 1]);
+
+        let mut list = List::build_from(vec![1, 2, 3, 4]);
+        if let Some((mut front, mut back)) = list.front_back_mut() {
+            *front += 10;
+            *back *= 10;
+        }
+        assert_eq!(list.to_vec(), vec![11, 2, 3, 40]);
+    }
+
+    #[test]
+    fn retain_indexed() {
+        let mut list = List::build_from(vec!['a', 'b', 'c', 'd']);
+        list.retain_indexed(|i, _| i % 2 == 0);
+        assert_eq!(list.to_vec(), vec!['a', 'c']);
+        assert_eq!(list.to_vec_rev(), vec!['c', 'a']);
+        assert_eq!(list.len(), 2);
+        list.retain_indexed(|_, _| false);
+        assert!(list.is_empty());
+        assert!(list.peek_back().is_none());
+    }
+
+    #[test]
+    fn into_iter_nth() {
+        let mut iter = List::build_from(1..=5).into_iter();
+        assert_eq!(iter.nth_back(1), Some(4));
+        assert_eq!(iter.nth(2), Some(3));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = List::build_from(1..=5).into_iter();
+        assert_eq!(iter.nth(2), Some(3));
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn from_singly() {
+        let mut singly = chapter_2_3::List::new();
+        singly.push(3); singly.push(2); singly.push(1);
+        let list = List::from_singly(&singly);
+        assert_eq!(list.len(), 3);
+        assert_eq!(*list.peek_front().unwrap(), 1);
+        assert_eq!(*list.peek_back().unwrap(), 3);
+        assert_eq!(list.to_vec_rev(), vec![3, 2, 1]);
+        assert_eq!(singly.len(), 3); //原来的单链表不受影响
+    }
+
+    #[test]
+    fn cursor_remove_while() {
+        let mut list = List::build_from(vec![1, 2, 0, 0, 0, 3, 0]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_while(|&x| x == 0), 3);
+        assert_eq!(*cursor.current().unwrap(), 3);
+        cursor.move_next();
+        assert_eq!(cursor.remove_while(|&x| x == 0), 1); //删到末尾
+        assert!(cursor.current().is_none());
+        assert_eq!(cursor.remove_while(|_| true), 0);
+        assert_eq!(list.to_vec(), vec![1, 2, 3]);
+        assert_eq!(list.to_vec_rev(), vec![3, 2, 1]);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn split_halves() {
+        let (front, back) = List::build_from(1..=4).split_halves();
+        assert_eq!((front.len(), back.len()), (2, 2));
+        assert_eq!(front.to_vec(), vec![1, 2]);
+        assert_eq!(front.to_vec_rev(), vec![2, 1]);
+        assert_eq!(back.to_vec(), vec![3, 4]);
+        assert_eq!(back.to_vec_rev(), vec![4, 3]);
+
+        let (front, back) = List::build_from(1..=5).split_halves();
+        assert_eq!((front.len(), back.len()), (3, 2));
+        assert_eq!(front.to_vec_rev(), vec![3, 2, 1]);
+        assert_eq!(back.to_vec(), vec![4, 5]);
+
+        let (front, back) = List::singleton(1).split_halves();
+        assert_eq!(front.to_vec(), vec![1]);
+        assert!(back.is_empty());
+        let (front, back) = List::<i32>::new().split_halves();
+        assert!(front.is_empty() && back.is_empty());
+    }
+
+    #[test]
+    fn swap_ends() {
+        let mut list = List::<i32>::new();
+        list.swap_ends();
+        assert!(list.is_empty());
+        let mut list = List::singleton(1);
+        list.swap_ends();
+        assert_eq!(list.to_vec(), vec![1]);
+
+        let mut list = List::build_from(vec![1, 2]);
+        list.swap_ends();
+        assert_eq!(list.to_vec(), vec![2, 1]);
+        let mut list = List::build_from(1..=4);
+        list.swap_ends();
+        assert_eq!(list.to_vec(), vec![4, 2, 3, 1]);
+        assert_eq!(list.to_vec_rev(), vec![1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn truncate_to_zero_frees_nodes() {
+        let elem = Rc::new(0);
+        let mut list = List::build_from(vec![elem.clone(), elem.clone(), elem.clone()]);
+        list.truncate(1);
+        assert_eq!(Rc::strong_count(&elem), 2); //被截掉的节点已经释放，没有因为prev/next的环泄漏
+        list.truncate(0);
+        assert!(list.is_empty());
+        assert!(list.peek_front().is_none() && list.peek_back().is_none());
+        assert_eq!(Rc::strong_count(&elem), 1);
+        list.push_back(elem.clone());
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn rfind() {
+        let list = List::build_from(vec![1, 2, 3, 2, 5]);
+        assert_eq!(list.rfind(|&x| x == 2), Some(3));
+        assert_eq!(list.rfind(|&x| x == 1), Some(0));
+        assert_eq!(list.rfind(|&x| x > 9), None);
+        assert_eq!(List::<i32>::new().rfind(|_| true), None);
+    }
+
+    #[test]
+    fn into_iter_rev() {
+        let list = List::build_from(vec![1, 2, 3]);
+        assert_eq!(list.into_iter_rev().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(List::<i32>::new().into_iter_rev().count(), 0);
+    }
+
+    #[test]
+    fn is_palindrome() {
+        assert!(List::build_from(vec![1, 2, 3, 2, 1]).is_palindrome());
+        assert!(List::build_from(vec![1, 2, 2, 1]).is_palindrome());
+        assert!(!List::build_from(vec![1, 2, 3]).is_palindrome());
+        assert!(!List::build_from(vec![1, 2]).is_palindrome());
+        assert!(List::singleton(1).is_palindrome());
+        assert!(List::<i32>::new().is_palindrome());
+    }
+
+    #[test]
+    fn assert_consistent() {
+        let mut list = List::build_from(1..=4);
+        list.assert_consistent();
+        list.pop_front();
+        list.push_back(5);
+        list.assert_consistent();
+        List::<i32>::new().assert_consistent();
+        List::singleton(1).assert_consistent();
+    }
+
+    #[test]
+    #[should_panic(expected = "inconsistent list: backward traversal reached 3 nodes but len is 4")]
+    fn assert_consistent_bad_prev() {
+        let list = List::build_from(1..=4);
+        let first = list.node_at(0);
+        list.node_at(2).unwrap().borrow_mut().prev = first; //从尾往头走会跳过2
+        list.assert_consistent();
+    }
+
+    #[test]
+    #[should_panic(expected = "inconsistent list: forward traversal reached 5 nodes but len is 4")]
+    fn assert_consistent_cycle() {
+        let list = List::build_from(1..=4);
+        let second = list.node_at(1);
+        list.node_at(3).unwrap().borrow_mut().next = second;
+        list.assert_consistent();
+    }
+
+    #[test]
+    fn pop_front_batch() {
+        let mut list = List::build_from(1..=5);
+        assert_eq!(list.pop_front_batch(2), vec![1, 2]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(*list.peek_front().unwrap(), 3);
+        assert_eq!(list.pop_front_batch(10), vec![3, 4, 5]);
+        assert!(list.is_empty());
+        assert!(list.peek_back().is_none());
+        assert!(list.pop_front_batch(3).is_empty());
+        assert!(List::<i32>::new().pop_front_batch(0).is_empty());
+    }
 }
 
 use std::cell::Cell;